@@ -0,0 +1,59 @@
+use core::mem;
+use core::slice;
+
+// What the `to_bytes()` and `from_bytes()` that `emit_pod_bytes` generates
+// call, for plain old data structs.
+
+// The bytes of `value`, including its padding.
+//
+// Safety: every byte of `value` has to be initialized, padding included. That
+// holds for a struct that the game wrote, or one that came from `from_bytes()`,
+// but not necessarily for one built in Rust, whose padding is uninitialized.
+pub unsafe fn to_bytes<T>(value: &T) -> &[u8] {
+    slice::from_raw_parts((value as *const T).cast(), mem::size_of::<T>())
+}
+
+// `bytes` as a `T`, or `None` if `bytes` is too short or misaligned for one.
+//
+// Safety: any bit pattern has to be a valid `T`.
+pub unsafe fn from_bytes<T>(bytes: &[u8]) -> Option<&T> {
+    if bytes.len() < mem::size_of::<T>() || bytes.as_ptr().align_offset(mem::align_of::<T>()) != 0 {
+        None
+    } else {
+        Some(&*bytes.as_ptr().cast::<T>())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[repr(C)]
+    #[derive(Debug, PartialEq)]
+    struct Pod {
+        a: u32,
+        b: u16,
+        pad: [u8; 2],
+    }
+
+    #[test]
+    fn round_trip() {
+        let pod = Pod { a: 0x1234_5678, b: 0xabcd, pad: [1, 2] };
+        let bytes = unsafe { to_bytes(&pod) };
+
+        assert_eq!(bytes, [0x78, 0x56, 0x34, 0x12, 0xcd, 0xab, 1, 2]);
+        assert_eq!(unsafe { from_bytes::<Pod>(bytes) }, Some(&pod));
+    }
+
+    #[test]
+    fn rejects_short_and_misaligned_bytes() {
+        #[repr(align(4))]
+        struct Aligned([u8; 12]);
+
+        let aligned = Aligned([0; 12]);
+
+        assert!(unsafe { from_bytes::<Pod>(&aligned.0[..7]) }.is_none());
+        assert!(unsafe { from_bytes::<Pod>(&aligned.0[1..9]) }.is_none());
+        assert!(unsafe { from_bytes::<Pod>(&aligned.0[4..12]) }.is_some());
+    }
+}
@@ -0,0 +1,144 @@
+use crate::{UClass, UObject, MAX_OUTERS};
+
+// Implemented by every generated class. The class is looked up by its full
+// name the first time it's needed, so this is null if the class isn't loaded.
+pub trait StaticClass {
+    unsafe fn static_class() -> *const UClass;
+}
+
+// `object.cast::<T>()`, but only if `object` is a `T` or derives from it.
+// Returns `None` for null objects and for classes that aren't loaded.
+pub unsafe fn try_cast<T: StaticClass>(object: *mut UObject) -> Option<*mut T> {
+    let class = T::static_class();
+
+    if object.is_null() || class.is_null() || !(*object).is(class) {
+        None
+    } else {
+        Some(object.cast())
+    }
+}
+
+// The closest outer of `object` that is a `T` or derives from one, e.g. a
+// component's actor. Gives up after `MAX_OUTERS` outers.
+pub unsafe fn outer_as<T: StaticClass>(object: *const UObject) -> Option<*mut T> {
+    let mut outer = (*object).outer();
+
+    for _ in 0..MAX_OUTERS {
+        if outer.is_null() {
+            break;
+        }
+
+        if let Some(outer) = try_cast::<T>(outer) {
+            return Some(outer);
+        }
+
+        outer = (*outer).outer();
+    }
+
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use core::mem::MaybeUninit;
+    use core::ptr;
+    use std::boxed::Box;
+    use std::sync::OnceLock;
+
+    fn leak<T>() -> *mut T {
+        Box::leak(Box::new(unsafe { MaybeUninit::<T>::zeroed().assume_init() }))
+    }
+
+    fn class(bases: &[*const UClass]) -> *const UClass {
+        let class = leak::<UClass>();
+
+        unsafe {
+            let bases = bases.iter().map(|&base| base.cast()).collect::<std::vec::Vec<_>>();
+            (*class).set_bases(&bases);
+        }
+
+        class
+    }
+
+    fn object(class: *const UClass, outer: *mut UObject) -> *mut UObject {
+        let object = leak::<UObject>();
+
+        unsafe {
+            (*object).set_class(class);
+            (*object).set_outer(outer);
+        }
+
+        object
+    }
+
+    // `Weapon` and `Grenade` both derive from `Actor`.
+    fn classes() -> &'static [usize; 3] {
+        static CLASSES: OnceLock<[usize; 3]> = OnceLock::new();
+
+        CLASSES.get_or_init(|| {
+            let actor = class(&[]);
+            let weapon = class(&[actor]);
+            let grenade = class(&[actor]);
+            [actor as usize, weapon as usize, grenade as usize]
+        })
+    }
+
+    macro_rules! classes {
+        ($($name:ident = $index:expr,)*) => {$(
+            struct $name;
+
+            impl StaticClass for $name {
+                unsafe fn static_class() -> *const UClass {
+                    classes()[$index] as *const UClass
+                }
+            }
+        )*};
+    }
+
+    classes! {
+        Actor = 0,
+        Weapon = 1,
+        Grenade = 2,
+    }
+
+    // A class that isn't loaded.
+    struct Unloaded;
+
+    impl StaticClass for Unloaded {
+        unsafe fn static_class() -> *const UClass {
+            ptr::null()
+        }
+    }
+
+    #[test]
+    fn try_cast_only_succeeds_for_the_class_and_its_bases() {
+        unsafe {
+            let weapon = object(Weapon::static_class(), ptr::null_mut());
+
+            assert_eq!(try_cast::<Weapon>(weapon), Some(weapon.cast()));
+            assert_eq!(try_cast::<Actor>(weapon), Some(weapon.cast()));
+            assert_eq!(try_cast::<Grenade>(weapon), None);
+            assert_eq!(try_cast::<Unloaded>(weapon), None);
+            assert_eq!(try_cast::<Weapon>(ptr::null_mut()), None);
+        }
+    }
+
+    #[test]
+    fn outer_as_finds_the_closest_matching_outer() {
+        unsafe {
+            let unrelated = class(&[]);
+            let package = object(unrelated, ptr::null_mut());
+            let actor = object(Actor::static_class(), package);
+            let weapon = object(Weapon::static_class(), actor);
+            let component = object(unrelated, weapon);
+
+            assert_eq!(outer_as::<Weapon>(component), Some(weapon.cast()));
+            assert_eq!(outer_as::<Actor>(component), Some(weapon.cast()));
+            assert_eq!(outer_as::<Actor>(weapon), Some(actor.cast()));
+            assert_eq!(outer_as::<Grenade>(component), None);
+        }
+    }
+}
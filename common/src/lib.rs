@@ -2,7 +2,7 @@
 #![allow(non_snake_case, non_upper_case_globals, non_camel_case_types)]
 #![allow(clippy::missing_safety_doc)]
 
-#[cfg(not(debug_assertions))]
+#[cfg(all(not(test), panic = "abort", not(debug_assertions)))]
 #[panic_handler]
 fn panic(_: &core::panic::PanicInfo) -> ! {
     extern "Rust" {
@@ -13,7 +13,7 @@ fn panic(_: &core::panic::PanicInfo) -> ! {
     unsafe { f() }
 }
 
-#[cfg(debug_assertions)]
+#[cfg(all(not(test), panic = "abort", debug_assertions))]
 #[panic_handler]
 fn panic(_: &core::panic::PanicInfo) -> ! {
     loop {}
@@ -25,6 +25,11 @@ use core::ops::{Deref, DerefMut};
 use core::ptr::{self, NonNull};
 use core::slice;
 
+pub mod bytes;
+
+mod cast;
+pub use cast::*;
+
 mod fmt;
 pub use fmt::*;
 
@@ -34,12 +39,29 @@ pub use name::*;
 mod object;
 pub use object::*;
 
+mod reflected;
+pub use reflected::*;
+
 pub mod list;
 pub use list::*;
 
+mod log;
+pub use crate::log::*;
+
+mod map;
+pub use map::*;
+
+mod memory;
+pub use memory::*;
+
+mod spawn;
+pub use spawn::*;
+
 mod split;
 pub use split::*;
 
+mod string;
+
 pub mod timer;
 pub use timer::Timer;
 
@@ -87,6 +109,64 @@ impl<T> DerefMut for TArray<T> {
     }
 }
 
+// A hard object reference, i.e. an `FObjectProperty`. Same layout as `*mut T`.
+#[repr(transparent)]
+pub struct ObjPtr<T>(*mut T);
+
+impl<T> ObjPtr<T> {
+    pub fn get(&self) -> *mut T {
+        self.0
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.0.is_null()
+    }
+}
+
+impl<T> Clone for ObjPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ObjPtr<T> {}
+
+// A class reference, i.e. an `FClassProperty`: points to a `UClass` whose
+// instances are `T`s. Same layout as `*mut UClass`.
+#[repr(transparent)]
+pub struct ClassPtr<T> {
+    class: *mut UClass,
+    _marker: PhantomData<*const T>,
+}
+
+impl<T> ClassPtr<T> {
+    pub fn get(&self) -> *mut UClass {
+        self.class
+    }
+
+    pub fn is_null(&self) -> bool {
+        self.class.is_null()
+    }
+}
+
+impl<T> Clone for ClassPtr<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ClassPtr<T> {}
+
+// The engine's name for the same thing.
+pub type TSubclassOf<T> = ClassPtr<T>;
+
+const _: () = assert!(core::mem::size_of::<ObjPtr<u8>>() == 8);
+const _: () = assert!(core::mem::size_of::<ClassPtr<u8>>() == 8);
+// `PhantomData` mustn't change the alignment either, even for an overaligned
+// `T`.
+const _: () = assert!(core::mem::align_of::<ClassPtr<u128>>() == core::mem::align_of::<*mut UClass>());
+const _: () = assert!(core::mem::size_of::<TWeakObjectPtr<u8>>() == 8);
+
 #[derive(Copy, Clone)]
 #[repr(C)]
 pub struct FString {
@@ -95,7 +175,7 @@ pub struct FString {
     pub capacity: i32,
 }
 
-impl<'a> From<&'a [u16]> for FString {
+impl From<&[u16]> for FString {
     fn from(s: &[u16]) -> FString {
         FString {
             data: s.as_ptr(),
@@ -156,6 +236,54 @@ impl<T> TWeakObjectPtr<T> {
     }
 }
 
+// A pointer to an object we hold on to across frames, that can tell whether the
+// object is still alive. Like `FWeakObjectPtr`, it remembers the object's slot
+// in `GUObjectArray` and that slot's serial number. The engine only assigns a
+// serial number the first time something makes a weak pointer to the object,
+// so without one the handle also checks that the slot still holds the same
+// address.
+pub struct ObjectHandle<T> {
+    object: *mut T,
+    index: i32,
+    serial_number: i32,
+}
+
+impl<T> Clone for ObjectHandle<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for ObjectHandle<T> {}
+
+impl<T> ObjectHandle<T> {
+    pub unsafe fn new(object: *mut T) -> Self {
+        let index = (*object.cast::<UObject>()).InternalIndex;
+        let item = (*GUObjectArray).index_to_object(index);
+
+        ObjectHandle {
+            object,
+            index,
+            serial_number: if item.is_null() { 0 } else { (*item).SerialNumber },
+        }
+    }
+
+    // The object, or null if it has been destroyed since.
+    pub unsafe fn get(&self) -> *mut T {
+        let item = (*GUObjectArray).index_to_object(self.index);
+
+        if item.is_null()
+            || (*item).Object != self.object.cast()
+            || (self.serial_number != 0 && (*item).SerialNumber != self.serial_number)
+            || !(*item).is_valid()
+        {
+            ptr::null_mut()
+        } else {
+            self.object
+        }
+    }
+}
+
 #[repr(C)]
 pub struct FScriptDelegate {
     Object: FWeakObjectPtr,
@@ -255,3 +383,56 @@ pub unsafe fn init_globals(module: &win::Module) -> Result<(), Error> {
     FUObjectArray::init(module)?;
     Ok(())
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::any;
+    use core::mem;
+
+    struct Grenade;
+
+    #[repr(C)]
+    struct Loadout {
+        GrenadeClass: TSubclassOf<Grenade>,
+        Count: i32,
+    }
+
+    fn target<T>(_: TSubclassOf<T>) -> &'static str {
+        any::type_name::<T>()
+    }
+
+    #[test]
+    fn class_ptrs_keep_their_target_type_and_pointer_layout() {
+        let class = NonNull::<UClass>::dangling().as_ptr();
+        let loadout = Loadout {
+            GrenadeClass: ClassPtr { class, _marker: PhantomData },
+            Count: 2,
+        };
+
+        let copy = loadout.GrenadeClass;
+        assert_eq!(copy.get(), class);
+        assert!(!copy.is_null());
+        assert!(target(copy).ends_with("::Grenade"));
+
+        assert_eq!(mem::offset_of!(Loadout, Count), mem::size_of::<*mut UClass>());
+        assert_eq!(mem::align_of::<Loadout>(), mem::align_of::<*mut UClass>());
+    }
+
+    #[test]
+    fn pointer_iterators_skip_null_entries() {
+        let (mut first, mut second) = (0u64, 0u64);
+        let (first, second): (*mut u64, *mut u64) = (&mut first, &mut second);
+        let mut pointers = [first, ptr::null_mut(), second, ptr::null_mut()];
+        let items = TArray {
+            data: pointers.as_mut_ptr(),
+            len: 4,
+            capacity: 4,
+        };
+
+        // The body of a generated `iter_{field}()`.
+        let iter = items.iter().copied().filter(|p| !p.is_null());
+
+        assert!(iter.eq([first, second]));
+    }
+}
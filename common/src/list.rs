@@ -46,7 +46,7 @@ impl<T, const N: usize> List<T, N> {
         }
     }
 
-    pub fn iter(&self) -> Iter<T> {
+    pub fn iter(&self) -> Iter<'_, T> {
         self.as_slice().iter()
     }
 
@@ -96,7 +96,7 @@ impl<T, const N: usize> List<T, N> {
         }
     }
 
-    fn as_mut_slice(&mut self) -> &mut [T] {
+    pub fn as_mut_slice(&mut self) -> &mut [T] {
         unsafe {
             // SAFETY: We ensure that &self.data[..self.len] contains initialized values.
             slice::from_raw_parts_mut(self.data.as_mut_ptr() as *mut T, self.len)
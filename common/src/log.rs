@@ -0,0 +1,144 @@
+use crate::win;
+use core::fmt::{self, Arguments, Write};
+
+// Where `log!` sends its messages. The generator and the hooks both log
+// through `log!`, so `set_logger()` redirects either of them at once.
+pub trait Log {
+    fn log(&self, args: Arguments);
+}
+
+// The console that `win::AllocConsole()` opened. The default.
+pub struct ConsoleLog;
+
+// `OutputDebugStringW()`, for a debugger or DebugView. Works without a console.
+pub struct DebugLog;
+
+// Drops every message.
+pub struct NoLog;
+
+#[cfg(windows)]
+static mut LOGGER: &dyn Log = &ConsoleLog;
+
+// Tests run on the host, which has no console to write to.
+#[cfg(not(windows))]
+static mut LOGGER: &dyn Log = &NoLog;
+
+// Not synchronized: call this before anything that might log on another
+// thread, e.g. first thing in `on_attach()`.
+pub unsafe fn set_logger(logger: &'static dyn Log) {
+    LOGGER = logger;
+}
+
+pub unsafe fn logger() -> &'static dyn Log {
+    LOGGER
+}
+
+// Writes straight to the console, for output that isn't a log message, e.g.
+// a struct printed for the user.
+pub struct Console;
+
+impl Write for Console {
+    fn write_str(&mut self, text: &str) -> Result<(), fmt::Error> {
+        unsafe {
+            #[allow(clippy::cast_possible_truncation)]
+            win::WriteConsoleA(
+                win::GetStdHandle(win::STD_OUTPUT_HANDLE),
+                text.as_ptr(),
+                text.len() as u32,
+                core::ptr::null_mut(),
+                core::ptr::null_mut(),
+            );
+        }
+
+        Ok(())
+    }
+}
+
+impl Log for ConsoleLog {
+    fn log(&self, args: Arguments) {
+        let _ = writeln!(Console, "{}", args);
+    }
+}
+
+impl Log for DebugLog {
+    fn log(&self, args: Arguments) {
+        // `OutputDebugStringW()` wants null-terminated UTF-16, so convert in
+        // chunks. A debugger shows the chunks of one message as one line.
+        struct Chunks {
+            buffer: [u16; 256],
+            len: usize,
+        }
+
+        impl Chunks {
+            fn flush(&mut self) {
+                if let Some(end) = self.buffer.get_mut(self.len) {
+                    *end = 0;
+                    unsafe { win::OutputDebugStringW(self.buffer.as_ptr()) };
+                }
+
+                self.len = 0;
+            }
+        }
+
+        impl Write for Chunks {
+            fn write_str(&mut self, text: &str) -> Result<(), fmt::Error> {
+                for unit in text.encode_utf16() {
+                    // Keep room for the terminator.
+                    if self.len + 1 >= self.buffer.len() {
+                        self.flush();
+                    }
+
+                    if let Some(slot) = self.buffer.get_mut(self.len) {
+                        *slot = unit;
+                        self.len += 1;
+                    }
+                }
+
+                Ok(())
+            }
+        }
+
+        let mut chunks = Chunks {
+            buffer: [0; 256],
+            len: 0,
+        };
+
+        let _ = writeln!(chunks, "{}", args);
+        chunks.flush();
+    }
+}
+
+impl Log for NoLog {
+    fn log(&self, _: Arguments) {}
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::List;
+    use std::sync::Mutex;
+
+    // Collects what `log!` writes.
+    struct Buffer(Mutex<List<u8, 256>>);
+
+    impl Log for Buffer {
+        fn log(&self, args: Arguments) {
+            let _ = writeln!(self.0.lock().unwrap(), "{}", args);
+        }
+    }
+
+    static BUFFER: Buffer = Buffer(Mutex::new(List::new()));
+
+    #[test]
+    fn set_logger_redirects_log() {
+        unsafe { set_logger(&BUFFER) };
+        crate::log!("{} rounds left", 3);
+
+        unsafe { set_logger(&NoLog) };
+        crate::log!("dropped");
+
+        assert_eq!(BUFFER.0.lock().unwrap().as_slice(), b"3 rounds left\n");
+    }
+}
@@ -0,0 +1,186 @@
+use crate::TArray;
+use core::mem;
+
+// UE4's TMap<K, V>. It's a TSet of key-value pairs, which in turn is a sparse
+// array of elements plus a hash used for lookups. We only read maps, so the
+// hash is kept opaque and lookups are linear.
+
+#[repr(C)]
+pub struct TPair<K, V> {
+    pub Key: K,
+    pub Value: V,
+}
+
+#[repr(C)]
+struct TSetElement<T> {
+    Value: T,
+    HashNextId: i32,
+    HashIndex: i32,
+}
+
+// TBitArray<FDefaultBitArrayAllocator>, which keeps up to 128 bits inline.
+#[repr(C)]
+struct TBitArray {
+    InlineData: [u32; 4],
+    SecondaryData: *const u32,
+    NumBits: i32,
+    MaxBits: i32,
+}
+
+impl TBitArray {
+    unsafe fn get(&self, index: usize) -> bool {
+        if index >= self.NumBits as usize {
+            return false;
+        }
+
+        let words = if self.SecondaryData.is_null() {
+            self.InlineData.as_ptr()
+        } else {
+            self.SecondaryData
+        };
+
+        *words.add(index / 32) & (1 << (index % 32)) != 0
+    }
+}
+
+#[repr(C)]
+struct TSparseArray<T> {
+    Data: TArray<T>,
+    AllocationFlags: TBitArray,
+    FirstFreeIndex: i32,
+    NumFreeIndices: i32,
+}
+
+#[repr(C)]
+struct FHashAllocator {
+    InlineHash: i32,
+    SecondaryHash: *const i32,
+}
+
+#[repr(C)]
+pub struct TSet<T> {
+    Elements: TSparseArray<TSetElement<T>>,
+    Hash: FHashAllocator,
+    HashSize: i32,
+}
+
+impl<T> TSet<T> {
+    pub fn len(&self) -> usize {
+        let elements = &self.Elements;
+        (elements.Data.len - elements.NumFreeIndices).max(0) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &T> {
+        let elements = &self.Elements;
+
+        elements
+            .Data
+            .iter()
+            .enumerate()
+            .filter(move |(i, _)| unsafe { elements.AllocationFlags.get(*i) })
+            .map(|(_, element)| &element.Value)
+    }
+}
+
+#[repr(C)]
+pub struct TMap<K, V> {
+    Pairs: TSet<TPair<K, V>>,
+}
+
+impl<K, V> TMap<K, V> {
+    pub fn len(&self) -> usize {
+        self.Pairs.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.Pairs.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.Pairs.iter().map(|pair| (&pair.Key, &pair.Value))
+    }
+
+    pub fn get(&self, key: &K) -> Option<&V>
+    where
+        K: PartialEq,
+    {
+        self.iter().find(|(k, _)| *k == key).map(|(_, v)| v)
+    }
+}
+
+// The generator only emits `TMap` for map properties of this size.
+pub const TMAP_SIZE: usize = 0x50;
+
+const _: () = assert!(mem::size_of::<TMap<u8, u8>>() == TMAP_SIZE);
+const _: () = assert!(mem::size_of::<TMap<u64, [u8; 0x30]>>() == TMAP_SIZE);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::ptr;
+
+    fn element<K, V>(key: K, value: V) -> TSetElement<TPair<K, V>> {
+        TSetElement {
+            Value: TPair { Key: key, Value: value },
+            HashNextId: -1,
+            HashIndex: 0,
+        }
+    }
+
+    // A map over `elements`, where only the slots set in `allocated` hold a
+    // pair.
+    fn map<K, V>(elements: &mut [TSetElement<TPair<K, V>>], allocated: u32) -> TMap<K, V> {
+        let len = elements.len() as i32;
+
+        TMap {
+            Pairs: TSet {
+                Elements: TSparseArray {
+                    Data: TArray {
+                        data: elements.as_mut_ptr(),
+                        len,
+                        capacity: len,
+                    },
+                    AllocationFlags: TBitArray {
+                        InlineData: [allocated, 0, 0, 0],
+                        SecondaryData: ptr::null(),
+                        NumBits: len,
+                        MaxBits: 128,
+                    },
+                    FirstFreeIndex: -1,
+                    NumFreeIndices: len - allocated.count_ones() as i32,
+                },
+                Hash: FHashAllocator {
+                    InlineHash: 0,
+                    SecondaryHash: ptr::null(),
+                },
+                HashSize: 0,
+            },
+        }
+    }
+
+    #[test]
+    fn iter_skips_free_slots() {
+        let mut elements = [element(1, 1.0f32), element(2, 2.0), element(3, 3.0)];
+        let map = map(&mut elements, 0b101);
+
+        assert_eq!(map.len(), 2);
+        assert!(map.iter().eq([(&1, &1.0), (&3, &3.0)]));
+        assert_eq!(map.get(&3), Some(&3.0));
+        assert_eq!(map.get(&2), None);
+    }
+
+    #[test]
+    fn a_field_after_a_map_follows_its_reflected_size() {
+        #[repr(C)]
+        struct Fields {
+            map: TMap<i32, f32>,
+            after: i32,
+        }
+
+        assert_eq!(mem::offset_of!(Fields, after), TMAP_SIZE);
+    }
+}
@@ -0,0 +1,63 @@
+use crate::{win, GUObjectArray, ObjectIterator, UObject};
+
+use core::ffi::c_void;
+use core::iter::Copied;
+use core::slice;
+
+// Where tools like the SDK generator get the objects they walk. `LiveMemory`
+// reads the game's `GUObjectArray`, while `Snapshot` serves objects that were
+// laid out elsewhere (e.g. loaded from a dump), so a tool can be exercised
+// without being injected into the game.
+//
+// Only object enumeration and readability checks go through this trait for
+// now. Field reads on the returned objects are still plain pointer
+// dereferences, so a snapshot must contain fully formed objects (including
+// their classes, outers, and names).
+pub trait GameMemory {
+    type Objects<'a>: Iterator<Item = *mut UObject>
+    where
+        Self: 'a;
+
+    unsafe fn objects(&self) -> Self::Objects<'_>;
+
+    // Whether `[address, address + len)` can be read, see `win::is_readable()`.
+    unsafe fn is_readable(&self, address: *const c_void, len: usize) -> bool;
+}
+
+pub struct LiveMemory;
+
+impl GameMemory for LiveMemory {
+    type Objects<'a> = ObjectIterator;
+
+    unsafe fn objects(&self) -> ObjectIterator {
+        (*GUObjectArray).iter()
+    }
+
+    unsafe fn is_readable(&self, address: *const c_void, len: usize) -> bool {
+        win::is_readable(address, len)
+    }
+}
+
+pub struct Snapshot<'a> {
+    objects: &'a [*mut UObject],
+}
+
+impl<'a> Snapshot<'a> {
+    pub const fn new(objects: &'a [*mut UObject]) -> Self {
+        Self { objects }
+    }
+}
+
+impl<'s> GameMemory for Snapshot<'s> {
+    type Objects<'a> = Copied<slice::Iter<'a, *mut UObject>> where Self: 'a;
+
+    unsafe fn objects(&self) -> Self::Objects<'_> {
+        self.objects.iter().copied()
+    }
+
+    // A snapshot is laid out by whoever made it, so anything it points to is
+    // taken to be there.
+    unsafe fn is_readable(&self, address: *const c_void, _: usize) -> bool {
+        !address.is_null()
+    }
+}
@@ -4,7 +4,7 @@ use crate::Error;
 
 use core::cmp::Ordering;
 use core::ffi::c_void;
-use core::fmt::{self, Display, Formatter};
+use core::fmt::{self, Debug, Display, Formatter};
 use core::mem;
 use core::ptr;
 use core::str;
@@ -37,13 +37,26 @@ impl FName {
     pub fn number(&self) -> u32 {
         self.Number
     }
+
+    // Looks up an existing name. Names compare case-insensitively, like in the
+    // engine. This walks the whole name pool, so look names up once and keep
+    // them.
+    pub unsafe fn find(text: &str) -> Option<FName> {
+        (*NamePoolData)
+            .iter()
+            .find(|&(_, entry)| (*entry).text().eq_ignore_ascii_case(text))
+            .map(|(id, _)| FName {
+                ComparisonIndex: id,
+                Number: 0,
+            })
+    }
 }
 
 impl Display for FName {
     fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
         unsafe {
             if self.number() == 0 {
-                self.text().fmt(f)
+                Display::fmt(self.text(), f)
             } else {
                 write!(f, "{}_{}", self.text(), self.number() - 1)
             }
@@ -51,6 +64,12 @@ impl Display for FName {
     }
 }
 
+impl Debug for FName {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        write!(f, "\"{}\"", self)
+    }
+}
+
 #[derive(Copy, Clone)]
 #[repr(C)]
 pub struct FNameEntryId {
@@ -143,7 +162,7 @@ impl FNamePool {
         Ok(())
     }
 
-    pub unsafe fn iter(&self) -> NameIterator {
+    pub unsafe fn iter(&self) -> NameIterator<'_> {
         let first_block_size = if self.CurrentBlock > 0 {
             BlockSizeBytes
         } else {
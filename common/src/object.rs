@@ -21,7 +21,7 @@ const NumElementsPerChunk: usize = 64 * 1024;
 // The maximum number of outers we can store in an array.
 // Set to a large enough number to cover the outers length of all objects.
 // Used when constructing an object's name, as well as for name comparisons.
-const MAX_OUTERS: usize = 32;
+pub const MAX_OUTERS: usize = 32;
 
 #[derive(macros::NoPanicErrorDebug)]
 pub enum Error {
@@ -70,6 +70,13 @@ impl FUObjectArray {
             .unwrap_or(core::ptr::null_mut())
     }
 
+    #[inline(never)]
+    pub unsafe fn find_class(&self, name: &'static str) -> *const UClass {
+        self.find(name)
+            .map(|c| c.cast_const().cast())
+            .unwrap_or(core::ptr::null())
+    }
+
     pub unsafe fn find(&self, name: &'static str) -> Result<*mut UObject, Error> {
         // Do a short-circuiting name comparison.
 
@@ -135,7 +142,7 @@ impl FUObjectArray {
     }
 
     pub unsafe fn index_to_object(&self, index: i32) -> *const FUObjectItem {
-        if index < self.ObjObjects.NumElements {
+        if (0..self.ObjObjects.NumElements).contains(&index) {
             let index = index as usize;
             let chunk = *self.ObjObjects.Objects.add(index / NumElementsPerChunk);
             chunk.add(index % NumElementsPerChunk)
@@ -247,6 +254,17 @@ pub struct UObject {
     OuterPrivate: *mut UObject,
 }
 
+#[cfg(test)]
+impl UObject {
+    pub(crate) fn set_class(&mut self, class: *const UClass) {
+        self.ClassPrivate = class;
+    }
+
+    pub(crate) fn set_outer(&mut self, outer: *mut UObject) {
+        self.OuterPrivate = outer;
+    }
+}
+
 impl UObject {
     pub unsafe fn package(&self) -> *const UPackage {
         let mut top = self as *const UObject;
@@ -268,6 +286,14 @@ impl UObject {
         top.cast()
     }
 
+    pub fn class(&self) -> *const UClass {
+        self.ClassPrivate
+    }
+
+    pub fn outer(&self) -> *mut UObject {
+        self.OuterPrivate
+    }
+
     pub unsafe fn is(&self, class: *const UClass) -> bool {
         (*self.ClassPrivate).is(class.cast())
     }
@@ -276,10 +302,19 @@ impl UObject {
         (*self.ClassPrivate).ClassCastFlags.any(class)
     }
 
+    pub fn is_default_object(&self) -> bool {
+        const RF_ClassDefaultObject: u32 = 0x10;
+        self.ObjectFlags & RF_ClassDefaultObject != 0
+    }
+
     pub unsafe fn name(&self) -> &str {
         self.NamePrivate.text()
     }
 
+    pub fn outer_chain(&self) -> OuterChain<'_> {
+        OuterChain(self)
+    }
+
     pub unsafe fn process_event(
         this: *mut UObject,
         function: *mut UFunction,
@@ -344,6 +379,42 @@ impl Display for UObject {
     }
 }
 
+// Formats the outers of an object from innermost to outermost, e.g.
+// `BP_Foo (Package)` for a Blueprint class. Stops after `MAX_OUTERS` so a
+// corrupted outer that loops back on itself can't hang us.
+pub struct OuterChain<'a>(&'a UObject);
+
+impl<'a> Display for OuterChain<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        unsafe {
+            let mut outer = self.0.OuterPrivate;
+
+            if outer.is_null() {
+                return write!(f, "(none)");
+            }
+
+            for i in 0..MAX_OUTERS {
+                if outer.is_null() {
+                    return Ok(());
+                }
+
+                if i > 0 {
+                    write!(f, " -> ")?;
+                }
+
+                write!(f, "{} ({})", (*outer).name(), (*(*outer).ClassPrivate).name())?;
+                outer = (*outer).OuterPrivate;
+            }
+
+            if !outer.is_null() {
+                write!(f, " -> ...")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
 #[repr(C)]
 pub struct UField {
     base: UObject,
@@ -383,6 +454,68 @@ impl UStruct {
     pub unsafe fn is(&self, parent: *const Self) -> bool {
         self.struct_base_chain.is(&(*parent).struct_base_chain)
     }
+
+    // The functions this struct declares itself, i.e. not its super struct's.
+    pub unsafe fn functions(&self) -> Functions {
+        Functions {
+            child: self.Children,
+            lagging: self.Children,
+            step: 0,
+        }
+    }
+}
+
+// Walks a `Children` list and yields its `UFunction`s. A corrupt list could
+// loop back on itself, so this also keeps a second pointer that moves at half
+// speed, and stops if the two ever meet.
+pub struct Functions {
+    child: *const UField,
+    lagging: *const UField,
+    step: u32,
+}
+
+impl Iterator for Functions {
+    type Item = *mut UFunction;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        unsafe {
+            while !self.child.is_null() {
+                let child = self.child;
+                self.child = (*child).Next;
+                self.step += 1;
+
+                if self.step.is_multiple_of(2) {
+                    self.lagging = (*self.lagging).Next;
+                }
+
+                if self.child == self.lagging {
+                    self.child = ptr::null();
+                }
+
+                if (*child).fast_is(EClassCastFlags::CASTCLASS_UFunction) {
+                    return Some(child.cast_mut().cast());
+                }
+            }
+
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+impl UStruct {
+    // Makes `self` derive from `bases`, listed from the root down, so tests can
+    // use `is()`. The chain is leaked because `self` keeps pointing into it.
+    pub(crate) unsafe fn set_bases(&mut self, bases: &[*const UStruct]) {
+        extern crate std;
+
+        let mut chain: std::vec::Vec<*const FStructBaseChain> =
+            bases.iter().map(|&base| ptr::addr_of!((*base).struct_base_chain)).collect();
+        chain.push(&self.struct_base_chain);
+
+        self.struct_base_chain.NumStructBasesInChainMinusOne = bases.len() as i32;
+        self.struct_base_chain.StructBaseChainArray = chain.leak().as_ptr();
+    }
 }
 
 impl_deref! { UStruct as UField }
@@ -406,10 +539,11 @@ impl UClass {
 }
 
 // struct FFrame : public FOutputDevice
-// TODO: fill in from UnrealEngine\Engine\Source\Runtime\CoreUObject\Public\UObject\Stack.h
+// UnrealEngine\Engine\Source\Runtime\CoreUObject\Public\UObject\Stack.h
 
 #[repr(C)]
 pub struct FOutputDevice {
+    vtable: *const c_void,
 	bSuppressEventTag: bool,
 	bAutoEmitLineTerminator: bool,
 }
@@ -426,7 +560,9 @@ pub struct FFrame {
 
 	MostRecentProperty: *mut c_void,
 	MostRecentPropertyAddress: *mut c_void,
-    FlowStack: crate::TArray<u32>,
+    // A `TArray<uint32, TInlineAllocator<8>>`: 8 inline elements, then the
+    // secondary allocation and `ArrayNum`/`ArrayMax`.
+    FlowStack: [u8; 48],
 	PreviousFrame: *mut c_void,
 	OutParms: *mut c_void,
 	PropertyChainForCompiledIn: *mut c_void,
@@ -434,6 +570,35 @@ pub struct FFrame {
 	bArrayContextFailed: bool,
 }
 
+impl FFrame {
+    // The frame `UObject::ProcessEvent()` builds for a native function: no
+    // bytecode, so the function's `P_GET_*` macros read each parameter from
+    // `Locals` in `ChildProperties` order instead. There's no vtable for
+    // `FOutputDevice`, so anything that logs to the frame, which the engine
+    // only does on script errors, will crash.
+    pub unsafe fn native(object: *mut UObject, function: *mut UFunction, locals: *mut c_void) -> FFrame {
+        FFrame {
+            base: FOutputDevice {
+                vtable: ptr::null(),
+                bSuppressEventTag: false,
+                bAutoEmitLineTerminator: true,
+            },
+            Node: function,
+            Object: object,
+            Code: ptr::null_mut(),
+            Locals: locals.cast(),
+            MostRecentProperty: ptr::null_mut(),
+            MostRecentPropertyAddress: ptr::null_mut(),
+            FlowStack: [0; 48],
+            PreviousFrame: ptr::null_mut(),
+            OutParms: ptr::null_mut(),
+            PropertyChainForCompiledIn: (&*function).ChildProperties.cast_mut().cast(),
+            CurrentNativeFunction: ptr::null_mut(),
+            bArrayContextFailed: false,
+        }
+    }
+}
+
 pub type FNativeFuncPtr =
     unsafe extern "C" fn(Context: *mut UObject, TheStack: *mut FFrame, Result: *mut c_void);
 
@@ -496,6 +661,27 @@ pub struct UFunction {
     pub Func: FNativeFuncPtr,
 }
 
+impl UFunction {
+    // Calls `this`'s native implementation on `object` directly, skipping
+    // `ProcessEvent()` and with it RPCs, Blueprint overrides and script
+    // functions, none of which this can call. `parameters` has to be laid out
+    // exactly as the function's parameters, the same struct a generated
+    // wrapper passes to `ProcessEvent()`; outputs, including the return value
+    // at `ReturnValueOffset`, are written back into it. See `call_native!`.
+    pub unsafe fn call_native(this: *mut UFunction, object: *mut UObject, parameters: *mut c_void) {
+        const NO_RETURN_VALUE: u16 = u16::MAX;
+
+        let result = if (*this).ReturnValueOffset == NO_RETURN_VALUE {
+            ptr::null_mut()
+        } else {
+            parameters.cast::<u8>().add(usize::from((*this).ReturnValueOffset)).cast()
+        };
+
+        let mut frame = FFrame::native(object, this, parameters);
+        ((*this).Func)(object, &mut frame, result);
+    }
+}
+
 #[repr(transparent)]
 pub struct EFunctionFlags(u32);
 
@@ -664,7 +850,7 @@ impl_deref! { UFunction as UStruct }
 
 #[repr(C)]
 pub struct FFieldClass {
-    pad0: [u8; 8],
+    pub Name: FName,
     pub Id: EClassCastFlags,
     pub CastFlags: EClassCastFlags,
     pad1: [u8; 40],
@@ -759,6 +945,69 @@ impl BitOr for EClassCastFlags {
     }
 }
 
+#[derive(Copy, Clone, PartialEq, Eq)]
+#[repr(transparent)]
+pub struct EPropertyFlags(pub u64);
+
+impl EPropertyFlags {
+    // Engine\Source\Runtime\CoreUObject\Public\UObject\ObjectMacros.h
+    pub const CPF_None: Self = Self(0);
+    pub const CPF_Edit: Self = Self(0x1); // < Property is user-settable in the editor.
+    pub const CPF_ConstParm: Self = Self(0x2); // < This is a constant function parameter
+    pub const CPF_BlueprintVisible: Self = Self(0x4); // < This property can be read by blueprint code
+    pub const CPF_ExportObject: Self = Self(0x8); // < Object can be exported with actor.
+    pub const CPF_BlueprintReadOnly: Self = Self(0x10); // < This property cannot be modified by blueprint code
+    pub const CPF_Net: Self = Self(0x20); // < Property is relevant to network replication.
+    pub const CPF_EditFixedSize: Self = Self(0x40); // < Indicates that elements of an array can be modified, but its size cannot be changed.
+    pub const CPF_Parm: Self = Self(0x80); // < Function/When call parameter.
+    pub const CPF_OutParm: Self = Self(0x100); // < Value is copied out after function call.
+    pub const CPF_ZeroConstructor: Self = Self(0x200); // < memset is fine for construction
+    pub const CPF_ReturnParm: Self = Self(0x400); // < Return value.
+    pub const CPF_DisableEditOnTemplate: Self = Self(0x800); // < Disable editing of this property on an archetype/sub-blueprint
+    pub const CPF_Transient: Self = Self(0x2000); // < Property is transient: shouldn't be saved or loaded, except for Blueprint CDOs.
+    pub const CPF_Config: Self = Self(0x4000); // < Property should be loaded/saved as permanent profile.
+    pub const CPF_DisableEditOnInstance: Self = Self(0x10000); // < Disable editing on an instance of this class
+    pub const CPF_EditConst: Self = Self(0x20000); // < Property is uneditable in the editor.
+    pub const CPF_GlobalConfig: Self = Self(0x40000); // < Load config from base class, not subclass.
+    pub const CPF_InstancedReference: Self = Self(0x80000); // < Property is a component references.
+    pub const CPF_DuplicateTransient: Self = Self(0x200000); // < Property should always be reset to the default value during any type of duplication (copy/paste, binary duplication, etc.)
+    pub const CPF_SubobjectReference: Self = Self(0x400000); // < Property contains subobject references (TSubobjectPtr)
+    pub const CPF_SaveGame: Self = Self(0x1000000); // < Property should be serialized for save games, this is only checked for game-specific archives with ArIsSaveGame
+    pub const CPF_NoClear: Self = Self(0x2000000); // < Hide clear (and browse) button.
+    pub const CPF_ReferenceParm: Self = Self(0x8000000); // < Value is passed by reference; CPF_OutParam and CPF_Param should also be set.
+    pub const CPF_BlueprintAssignable: Self = Self(0x10000000); // < MC Delegates only.  Property should be exposed for assigning in blueprint code
+    pub const CPF_Deprecated: Self = Self(0x20000000); // < Property is deprecated.  Read it from an archive, but don't save it.
+    pub const CPF_IsPlainOldData: Self = Self(0x40000000); // < If this is set, then the property can be memcopied instead of CopyCompleteValue / CopySingleValue
+    pub const CPF_RepSkip: Self = Self(0x80000000); // < Not replicated. For non replicated properties in replicated structs
+    pub const CPF_RepNotify: Self = Self(0x100000000); // < Notify actors when a property is replicated
+    pub const CPF_Interp: Self = Self(0x200000000); // < interpolatable property for use with matinee
+    pub const CPF_NonTransactional: Self = Self(0x400000000); // < Property isn't transacted
+    pub const CPF_EditorOnly: Self = Self(0x800000000); // < Property should only be loaded in the editor
+    pub const CPF_NoDestructor: Self = Self(0x1000000000); // < No destructor
+    pub const CPF_AutoWeak: Self = Self(0x4000000000); // < Only used for weak pointers, means the export type is autoweak
+    pub const CPF_ContainsInstancedReference: Self = Self(0x8000000000); // < Property contains component references.
+    pub const CPF_AssetRegistrySearchable: Self = Self(0x10000000000); // < asset instances will add properties with this flag to the asset registry automatically
+    pub const CPF_SimpleDisplay: Self = Self(0x20000000000); // < The property is visible by default in the editor details view
+    pub const CPF_AdvancedDisplay: Self = Self(0x40000000000); // < The property is advanced and not visible by default in the editor details view
+    pub const CPF_Protected: Self = Self(0x80000000000); // < property is protected from the perspective of script
+    pub const CPF_BlueprintCallable: Self = Self(0x100000000000); // < MC Delegates only.  Property should be exposed for calling in blueprint code
+    pub const CPF_BlueprintAuthorityOnly: Self = Self(0x200000000000); // < MC Delegates only.  This delegate accepts (only in blueprint) only events with BlueprintAuthorityOnly.
+    pub const CPF_TextExportTransient: Self = Self(0x400000000000); // < Property shouldn't be exported to text format (e.g. copy/paste)
+    pub const CPF_NonPIEDuplicateTransient: Self = Self(0x800000000000); // < Property should only be copied in PIE
+    pub const CPF_ExposeOnSpawn: Self = Self(0x1000000000000); // < Property is exposed on spawn
+    pub const CPF_PersistentInstance: Self = Self(0x2000000000000); // < A object referenced by the property is duplicated like a component. (Each actor should have an own instance.)
+    pub const CPF_UObjectWrapper: Self = Self(0x4000000000000); // < Property was parsed as a wrapper class like TSubclassOf<T>, FScriptInterface etc., rather than a USomething*
+    pub const CPF_HasGetValueTypeHash: Self = Self(0x8000000000000); // < This property can generate a meaningful hash value.
+    pub const CPF_NativeAccessSpecifierPublic: Self = Self(0x10000000000000); // < Public native access specifier
+    pub const CPF_NativeAccessSpecifierProtected: Self = Self(0x20000000000000); // < Protected native access specifier
+    pub const CPF_NativeAccessSpecifierPrivate: Self = Self(0x40000000000000); // < Private native access specifier
+    pub const CPF_SkipSerialization: Self = Self(0x80000000000000); // < Property shouldn't be serialized, can still be exported to text
+
+    pub fn contains(&self, flag: Self) -> bool {
+        self.0 & flag.0 == flag.0
+    }
+}
+
 #[derive(Copy, Clone)]
 #[repr(transparent)]
 pub struct EClassFlags(u32);
@@ -793,3 +1042,72 @@ impl UPackage {
         unsafe { str::from_utf8_unchecked(name) }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use core::mem::MaybeUninit;
+    use std::boxed::Box;
+    use std::vec::Vec;
+
+    fn leak<T>() -> *mut T {
+        Box::leak(Box::new(unsafe { MaybeUninit::<T>::zeroed().assume_init() }))
+    }
+
+    // Children of the given kinds, each linked to the next.
+    fn children(kinds: &[EClassCastFlags]) -> Vec<*mut UField> {
+        let children: Vec<*mut UField> = kinds
+            .iter()
+            .map(|&kind| unsafe {
+                let class = leak::<UClass>();
+                (*class).ClassCastFlags = kind;
+
+                let child = leak::<UFunction>().cast::<UField>();
+                (*child).set_class(class);
+                child
+            })
+            .collect();
+
+        for pair in children.windows(2) {
+            let (child, next) = (pair[0], pair[1]);
+            unsafe { (*child).Next = next };
+        }
+
+        children
+    }
+
+    fn functions(first: *mut UField) -> Vec<*mut UFunction> {
+        let structure = leak::<UStruct>();
+        unsafe { (*structure).Children = first };
+
+        // A bounded `collect()`, in case the walk never ends.
+        unsafe { (*structure).functions() }.take(16).collect()
+    }
+
+    const FUNCTION: EClassCastFlags = EClassCastFlags::CASTCLASS_UFunction;
+    const PROPERTY: EClassCastFlags = EClassCastFlags::CASTCLASS_UField;
+
+    #[test]
+    fn functions_skips_other_children() {
+        let children = children(&[FUNCTION, PROPERTY, FUNCTION, PROPERTY]);
+
+        assert_eq!(functions(children[0]), [children[0].cast(), children[2].cast()]);
+        assert!(functions(ptr::null_mut()).is_empty());
+    }
+
+    #[test]
+    fn functions_stops_at_a_cycle() {
+        let children = children(&[FUNCTION, PROPERTY, FUNCTION]);
+        let last = children[2];
+        unsafe { (*last).Next = children[0] };
+
+        let functions = functions(children[0]);
+
+        assert!(functions.len() < 16);
+        assert!(functions.contains(&children[0].cast()));
+        assert!(functions.contains(&children[2].cast()));
+        assert!(!functions.contains(&children[1].cast()));
+    }
+}
@@ -27,7 +27,7 @@ fn split_class_and_outers(name: &str) -> Result<(&[u8], &[u8]), Error> {
 impl<'name, const NUM_OUTERS: usize> TryFrom<&'name str> for FullName<'name, NUM_OUTERS> {
     type Error = Error;
 
-    fn try_from(full_name: &str) -> Result<FullName<NUM_OUTERS>, Self::Error> {
+    fn try_from(full_name: &str) -> Result<FullName<'_, NUM_OUTERS>, Self::Error> {
         let (class, outers) = split_class_and_outers(full_name)?;
 
         // Reverse split because outers are organized inside-out within an
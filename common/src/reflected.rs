@@ -0,0 +1,46 @@
+// Implemented by generated structs, classes, and enums when the SDK was
+// generated with `emit_reflected`. `NAME` is the engine's short name for the
+// type. `SIZE` and `ALIGN` are what the engine reports, i.e. the struct's
+// `PropertiesSize` and `MinAlignment`, which can be smaller than
+// `size_of::<Self>()` because Rust rounds sizes up to the alignment. Enums
+// report their integer representation's.
+pub trait Reflected {
+    const NAME: &'static str;
+    const SIZE: usize;
+    const ALIGN: usize;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use core::mem;
+
+    // What `emit_reflected` generates for a struct whose last field is a
+    // `u8`, so Rust pads it to a multiple of its alignment.
+    #[repr(C)]
+    struct WeaponState {
+        Ammo: i32,
+        bIsReloading: u8,
+    }
+
+    impl Reflected for WeaponState {
+        const NAME: &'static str = "WeaponState";
+        const SIZE: usize = 0x5;
+        const ALIGN: usize = 4;
+    }
+
+    fn padding<T: Reflected>() -> usize {
+        mem::size_of::<T>() - T::SIZE
+    }
+
+    fn matches_engine<T: Reflected>() -> bool {
+        mem::size_of::<T>() >= T::SIZE && mem::align_of::<T>() == T::ALIGN
+    }
+
+    #[test]
+    fn generic_code_reads_the_reflected_layout() {
+        assert_eq!(WeaponState::NAME, "WeaponState");
+        assert_eq!(padding::<WeaponState>(), 3);
+        assert!(matches_engine::<WeaponState>());
+    }
+}
@@ -0,0 +1,116 @@
+use crate::{ClassPtr, GUObjectArray, UClass, UFunction, UObject};
+use core::ffi::c_void;
+use core::ptr;
+
+// An FTransform (rotation quaternion, translation, and scale), which we only
+// ever copy through.
+#[derive(Copy, Clone)]
+#[repr(C, align(16))]
+struct FTransform([u8; 0x30]);
+
+#[repr(C)]
+struct BeginDeferredActorSpawnFromClassParameters {
+    WorldContextObject: *mut UObject,
+    ActorClass: *const UClass,
+    SpawnTransform: FTransform,
+    CollisionHandlingOverride: u8, // ESpawnActorCollisionHandlingMethod
+    Owner: *mut UObject,
+    ReturnValue: *mut UObject,
+}
+
+#[repr(C)]
+struct FinishSpawningActorParameters {
+    Actor: *mut UObject,
+    SpawnTransform: FTransform,
+    ReturnValue: *mut UObject,
+}
+
+static mut GAMEPLAY_STATICS: *mut UObject = ptr::null_mut();
+static mut BEGIN_DEFERRED_ACTOR_SPAWN_FROM_CLASS: *mut UFunction = ptr::null_mut();
+static mut FINISH_SPAWNING_ACTOR: *mut UFunction = ptr::null_mut();
+
+// Spawns an actor of `class` through `UGameplayStatics`, the same way a
+// Blueprint "Spawn Actor from Class" node does.
+//
+// `world_context` is any object that lives in the target world, such as the
+// world itself or a player character. `transform` must point to an
+// `FTransform` (the generated `CoreUObject::Transform`). `owner` may be null.
+//
+// The actor only spawns on the machine that calls this. A client spawning a
+// replicated actor gets a local copy that the server doesn't know about, so
+// anything gameplay-relevant should only be spawned while we're the host.
+//
+// Returns null if anything couldn't be found or the engine refused to spawn.
+pub unsafe fn spawn_actor(
+    world_context: *mut UObject,
+    class: *const UClass,
+    transform: *const c_void,
+    owner: *mut UObject,
+) -> *mut UObject {
+    if world_context.is_null() || class.is_null() || transform.is_null() {
+        return ptr::null_mut();
+    }
+
+    if GAMEPLAY_STATICS.is_null() {
+        GAMEPLAY_STATICS = (*GUObjectArray)
+            .find("GameplayStatics /Script/Engine.Default__GameplayStatics")
+            .unwrap_or(ptr::null_mut());
+        BEGIN_DEFERRED_ACTOR_SPAWN_FROM_CLASS = (*GUObjectArray)
+            .find_function("Function /Script/Engine.GameplayStatics.BeginDeferredActorSpawnFromClass");
+        FINISH_SPAWNING_ACTOR = (*GUObjectArray)
+            .find_function("Function /Script/Engine.GameplayStatics.FinishSpawningActor");
+    }
+
+    if GAMEPLAY_STATICS.is_null()
+        || BEGIN_DEFERRED_ACTOR_SPAWN_FROM_CLASS.is_null()
+        || FINISH_SPAWNING_ACTOR.is_null()
+    {
+        return ptr::null_mut();
+    }
+
+    let transform = transform.cast::<FTransform>().read_unaligned();
+
+    let mut begin = BeginDeferredActorSpawnFromClassParameters {
+        WorldContextObject: world_context,
+        ActorClass: class,
+        SpawnTransform: transform,
+        CollisionHandlingOverride: 0,
+        Owner: owner,
+        ReturnValue: ptr::null_mut(),
+    };
+
+    UObject::process_event(
+        GAMEPLAY_STATICS,
+        BEGIN_DEFERRED_ACTOR_SPAWN_FROM_CLASS,
+        (&mut begin as *mut BeginDeferredActorSpawnFromClassParameters).cast(),
+    );
+
+    if begin.ReturnValue.is_null() {
+        return ptr::null_mut();
+    }
+
+    let mut finish = FinishSpawningActorParameters {
+        Actor: begin.ReturnValue,
+        SpawnTransform: transform,
+        ReturnValue: ptr::null_mut(),
+    };
+
+    UObject::process_event(
+        GAMEPLAY_STATICS,
+        FINISH_SPAWNING_ACTOR,
+        (&mut finish as *mut FinishSpawningActorParameters).cast(),
+    );
+
+    finish.ReturnValue
+}
+
+// `spawn_actor()` for a class reference field, whose type says what the
+// spawned actor is.
+pub unsafe fn spawn_actor_of<T>(
+    world_context: *mut UObject,
+    class: ClassPtr<T>,
+    transform: *const c_void,
+    owner: *mut UObject,
+) -> *mut T {
+    spawn_actor(world_context, class.get(), transform, owner).cast()
+}
@@ -7,7 +7,7 @@ where
 }
 
 impl<'a, F: FnMut(u8) -> bool> SplitIterator<'a, F> {
-    pub fn new(source: &[u8], is_delimiter: F) -> SplitIterator<F> {
+    pub fn new(source: &[u8], is_delimiter: F) -> SplitIterator<'_, F> {
         SplitIterator {
             source,
             is_delimiter,
@@ -48,7 +48,7 @@ pub struct ReverseSplitIterator<'a> {
 }
 
 impl<'a> ReverseSplitIterator<'a> {
-    pub fn new(source: &[u8], delimiter: u8) -> ReverseSplitIterator {
+    pub fn new(source: &[u8], delimiter: u8) -> ReverseSplitIterator<'_> {
         ReverseSplitIterator { source, delimiter }
     }
 }
@@ -0,0 +1,170 @@
+use crate::{FString, FText, ITextData};
+
+use core::char::{self, REPLACEMENT_CHARACTER};
+use core::fmt::{self, Display, Formatter, Write};
+use core::slice;
+use core::str;
+
+impl FString {
+    // The UTF-16 code units, without the terminating nul that `len` counts.
+    pub fn as_slice(&self) -> &[u16] {
+        if self.data.is_null() || self.len <= 0 {
+            return &[];
+        }
+
+        let units = unsafe { slice::from_raw_parts(self.data, self.len as usize) };
+
+        match units.split_last() {
+            Some((0, rest)) => rest,
+            _ => units,
+        }
+    }
+
+    // Unpaired surrogates become U+FFFD.
+    pub fn chars(&self) -> impl Iterator<Item = char> + '_ {
+        char::decode_utf16(self.as_slice().iter().copied())
+            .map(|c| c.unwrap_or(REPLACEMENT_CHARACTER))
+    }
+
+    // Encodes as much of the string as fits into `buf` as UTF-8, cutting off
+    // at a character boundary.
+    pub fn to_str_lossy<'a>(&self, buf: &'a mut [u8]) -> &'a str {
+        let mut len = 0;
+
+        for c in self.chars() {
+            let Some(rest) = buf.get_mut(len..) else {
+                break;
+            };
+
+            if rest.len() < c.len_utf8() {
+                break;
+            }
+
+            len += c.encode_utf8(rest).len();
+        }
+
+        // SAFETY: `buf[..len]` is a sequence of whole `encode_utf8()` outputs.
+        unsafe { str::from_utf8_unchecked(buf.get_unchecked(..len)) }
+    }
+}
+
+impl Display for FString {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        for c in self.chars() {
+            f.write_char(c)?;
+        }
+
+        Ok(())
+    }
+}
+
+// `ITextData::GetDisplayString()`, the third virtual after the destructor and
+// `GetSourceString()`. Returns a reference to its `FString`.
+const GET_DISPLAY_STRING: usize = 2;
+
+impl FText {
+    // The localized string this text displays as, or null for an empty text.
+    pub unsafe fn display_string(&self) -> *const FString {
+        let data = self.TextData.Object;
+
+        if data.is_null() {
+            return core::ptr::null();
+        }
+
+        let get_display_string: unsafe extern "C" fn(*const ITextData) -> *const FString =
+            core::mem::transmute(*(*data).vtable.add(GET_DISPLAY_STRING));
+
+        get_display_string(data)
+    }
+}
+
+impl Display for FText {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        unsafe {
+            let string = self.display_string();
+
+            if string.is_null() {
+                Ok(())
+            } else {
+                (*string).fmt(f)
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::TSharedRef;
+    use core::ptr;
+    use std::string::{String, ToString};
+    use std::vec::Vec;
+
+    // What the engine stores, including the terminating nul.
+    fn utf16(s: &str) -> Vec<u16> {
+        s.encode_utf16().chain([0]).collect()
+    }
+
+    #[test]
+    fn ascii_strings_read_without_their_nul() {
+        let units = utf16("Gunner");
+        let string = FString::from(units.as_slice());
+        let mut buf = [0; 16];
+
+        assert_eq!(string.as_slice().len(), 6);
+        assert_eq!(string.to_str_lossy(&mut buf), "Gunner");
+        assert_eq!(string.to_string(), "Gunner");
+    }
+
+    #[test]
+    fn non_ascii_strings_are_cut_at_a_character_boundary() {
+        let units = utf16("Grüße 🪨");
+        let string = FString::from(units.as_slice());
+
+        assert_eq!(string.chars().collect::<String>(), "Grüße 🪨");
+        assert_eq!(string.to_str_lossy(&mut [0; 32]), "Grüße 🪨");
+
+        // "ü" takes two bytes, so only "Gr" fits into three.
+        assert_eq!(string.to_str_lossy(&mut [0; 3]), "Gr");
+        assert_eq!(string.to_str_lossy(&mut []), "");
+    }
+
+    #[test]
+    fn unpaired_surrogates_become_replacement_characters() {
+        let units = [0x44, 0xD83E, 0x47, 0];
+        let string = FString::from(&units[..]);
+
+        assert_eq!(string.to_string(), "D\u{FFFD}G");
+    }
+
+    #[test]
+    fn texts_display_their_display_string() {
+        static mut STRING: FString = FString {
+            data: ptr::null(),
+            len: 0,
+            capacity: 0,
+        };
+
+        unsafe extern "C" fn get_display_string(_: *const ITextData) -> *const FString {
+            ptr::addr_of!(STRING)
+        }
+
+        let units = utf16("Rock and Stone");
+        unsafe { *ptr::addr_of_mut!(STRING) = FString::from(units.as_slice()) };
+
+        let vtable = [ptr::null(), ptr::null(), get_display_string as *const usize];
+        let data = ITextData { vtable: vtable.as_ptr() };
+        let text = |object| FText {
+            TextData: TSharedRef {
+                Object: object,
+                SharedReferenceCount: ptr::null(),
+            },
+            Flags: 0,
+        };
+
+        assert_eq!(text(&data).to_string(), "Rock and Stone");
+        assert_eq!(text(ptr::null()).to_string(), "");
+    }
+}
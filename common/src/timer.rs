@@ -5,7 +5,7 @@ use core::fmt::{self, Display, Formatter, Write};
 static mut TICKS_PER_SECOND: i64 = 0;
 
 pub unsafe fn initialize_ticks_per_second() {
-    win::QueryPerformanceFrequency(&mut TICKS_PER_SECOND);
+    win::QueryPerformanceFrequency(core::ptr::addr_of_mut!(TICKS_PER_SECOND));
     crate::log!("TICKS_PER_SECOND = {}", FormattedTicks(TICKS_PER_SECOND));
 }
 
@@ -17,6 +17,12 @@ fn get_current_tick() -> i64 {
     tick
 }
 
+// Seconds since an arbitrary fixed point, for measuring time between calls.
+// Needs `initialize_ticks_per_second()` to have been called.
+pub fn now() -> f64 {
+    get_current_tick() as f64 / unsafe { TICKS_PER_SECOND } as f64
+}
+
 pub struct Timer<A: Display> {
     start_tick: i64,
     action: A,
@@ -1,29 +1,81 @@
+// Logs one line through `common::logger()`.
 #[macro_export]
 macro_rules! log {
     ($($arg:tt)*) => {{
-        use core::fmt::Write;
-
-        struct Stdout;
-
-        impl Write for Stdout {
-            fn write_str(&mut self, text: &str) -> Result<(), core::fmt::Error> { unsafe {
-                #[allow(clippy::cast_possible_truncation)]
-                $crate::win::WriteConsoleA(
-                    $crate::win::GetStdHandle($crate::win::STD_OUTPUT_HANDLE),
-                    text.as_ptr(),
-                    text.len() as u32,
-                    core::ptr::null_mut(),
-                    core::ptr::null_mut(),
-                );
-
-                Ok(())
-            }}
-        }
-
-        let _ = writeln!(&mut Stdout, $($arg)*);
+        #[allow(unused_unsafe)]
+        let logger = unsafe { $crate::logger() };
+        logger.log(format_args!($($arg)*));
     }}
 }
 
+// `common::call_native!(object, function, &mut parameters)` calls the native
+// implementation of the `*mut UFunction` `function` on `object`, see
+// `UFunction::call_native()`. The parameters are passed as they lie in memory,
+// like `ProcessEvent()` does, so `parameters` can be any `#[repr(C)]` struct
+// whose layout matches.
+#[macro_export]
+macro_rules! call_native {
+    ($object:expr, $function:expr, $parameters:expr) => {
+        $crate::UFunction::call_native(
+            $function,
+            ($object as *mut _ as *mut $crate::UObject),
+            ($parameters as *mut _ as *mut core::ffi::c_void),
+        )
+    };
+}
+
 pub fn align(x: usize, alignment: usize) -> usize {
     (x + alignment - 1) & !(alignment - 1)
 }
+
+// The byte offset of `$field` in `$Type`. Works in const contexts, like
+// `core::mem::offset_of!`, but only needs Rust 1.65 instead of 1.77.
+#[macro_export]
+macro_rules! offset_of {
+    ($Type:ty, $field:ident) => {{
+        let uninit = core::mem::MaybeUninit::<$Type>::uninit();
+        let base = uninit.as_ptr();
+
+        #[allow(unused_unsafe)]
+        unsafe {
+            let field = core::ptr::addr_of!((*base).$field);
+            (field as *const u8).offset_from(base as *const u8) as usize
+        }
+    }};
+}
+
+// The compact form of a generated struct, see `GeneratorOptions::compact_structs`
+// in `sdk_gen`. Expands to exactly what the verbose form spells out: a
+// `#[repr(C, align(N))]` struct with public fields, and for a derived struct a
+// private `base` field first plus `Deref`/`DerefMut` to it.
+#[macro_export]
+macro_rules! define_struct {
+    ($Name:ident: align($align:literal); $($(#[$meta:meta])* $field:ident: $Type:ty,)*) => {
+        #[repr(C, align($align))]
+        pub struct $Name {
+            $($(#[$meta])* pub $field: $Type,)*
+        }
+    };
+
+    ($Name:ident: align($align:literal), base($Base:ty); $($(#[$meta:meta])* $field:ident: $Type:ty,)*) => {
+        #[repr(C, align($align))]
+        pub struct $Name {
+            base: $Base,
+            $($(#[$meta])* pub $field: $Type,)*
+        }
+
+        impl core::ops::Deref for $Name {
+            type Target = $Base;
+
+            fn deref(&self) -> &Self::Target {
+                &self.base
+            }
+        }
+
+        impl core::ops::DerefMut for $Name {
+            fn deref_mut(&mut self) -> &mut Self::Target {
+                &mut self.base
+            }
+        }
+    };
+}
@@ -18,10 +18,24 @@ pub const STD_INPUT_HANDLE: u32 = 0xFFFF_FFF6;
 
 type ThreadProc = unsafe extern "system" fn(parameter: *mut c_void) -> u32;
 
-#[link(name = "Kernel32")]
+// MEMORY_BASIC_INFORMATION
+#[repr(C)]
+struct MemoryBasicInformation {
+    base_address: *mut c_void,
+    allocation_base: *mut c_void,
+    allocation_protect: u32,
+    partition_id: u16,
+    region_size: usize,
+    state: u32,
+    protect: u32,
+    typ: u32,
+}
+
+#[cfg_attr(windows, link(name = "Kernel32"))]
 extern "system" {
     pub fn AllocConsole() -> i32;
     fn CloseHandle(object: *mut c_void) -> i32;
+    fn CreateDirectoryA(path_name: *const u8, security_attributes: *mut c_void) -> i32;
     fn CreateFileA(
         file_name: *const u8,
         desired_access: u32,
@@ -49,8 +63,10 @@ extern "system" {
     pub fn FreeConsole() -> i32;
     pub fn FreeLibraryAndExitThread(dll: *mut c_void, exit_code: u32);
     pub fn GetCurrentProcess() -> *mut c_void;
+    fn GetLastError() -> u32;
     pub fn GetModuleHandleA(module_name: *const u8) -> *mut c_void;
     pub fn GetStdHandle(std_handle: u32) -> *mut c_void;
+    pub fn OutputDebugStringW(output_string: *const u16);
     pub fn ReadConsoleA(
         console_input: *mut c_void,
         buffer: *mut u8,
@@ -58,9 +74,20 @@ extern "system" {
         num_read: *mut u32,
         input_control: *mut c_void,
     ) -> i32;
+    fn SetFilePointerEx(
+        file: *mut c_void,
+        distance_to_move: i64,
+        new_file_pointer: *mut i64,
+        move_method: u32,
+    ) -> i32;
     pub fn Sleep(dwMilliseconds: u32);
     pub fn QueryPerformanceCounter(lpPerformanceCount: *mut i64) -> i32;
     pub fn QueryPerformanceFrequency(lpFrequency: *mut i64) -> i32;
+    fn VirtualQuery(
+        address: *const c_void,
+        buffer: *mut MemoryBasicInformation,
+        length: usize,
+    ) -> usize;
     pub fn VirtualProtect(
         lpAddress: *mut c_void,
         dwSize: usize,
@@ -83,7 +110,7 @@ extern "system" {
     ) -> i32;
 }
 
-#[link(name = "Bcrypt")]
+#[cfg_attr(windows, link(name = "Bcrypt"))]
 extern "system" {
     fn BCryptGenRandom(hAlgorithm: *mut c_void, pbBuffer: *mut u8, cbBuffer: u32, dwFlags: u32) -> i32;
 }
@@ -111,6 +138,55 @@ pub unsafe fn dll_main(
     1
 }
 
+// Whether all of `[address, address + len)` is committed memory that we're
+// allowed to read. This is only a snapshot: another thread can free the memory
+// right after we look.
+pub unsafe fn is_readable(address: *const c_void, len: usize) -> bool {
+    const MEM_COMMIT: u32 = 0x1000;
+    const PAGE_GUARD: u32 = 0x100;
+
+    // PAGE_READONLY, PAGE_READWRITE, PAGE_WRITECOPY, PAGE_EXECUTE_READ,
+    // PAGE_EXECUTE_READWRITE, and PAGE_EXECUTE_WRITECOPY. Anything else, e.g.
+    // PAGE_NOACCESS or PAGE_EXECUTE, can't be read.
+    const PAGE_READABLE: u32 = 0x02 | 0x04 | 0x08 | 0x20 | 0x40 | 0x80;
+
+    let mut cursor = address as usize;
+    let end = match cursor.checked_add(len) {
+        Some(end) => end,
+        None => return false,
+    };
+
+    while cursor < end {
+        let mut info = core::mem::MaybeUninit::<MemoryBasicInformation>::uninit();
+
+        let written = VirtualQuery(
+            cursor as *const c_void,
+            info.as_mut_ptr(),
+            core::mem::size_of::<MemoryBasicInformation>(),
+        );
+
+        if written == 0 {
+            return false;
+        }
+
+        let info = info.assume_init();
+
+        if info.state != MEM_COMMIT || info.protect & PAGE_READABLE == 0 || info.protect & PAGE_GUARD != 0 {
+            return false;
+        }
+
+        let region_end = (info.base_address as usize).saturating_add(info.region_size);
+
+        if region_end <= cursor {
+            return false;
+        }
+
+        cursor = region_end;
+    }
+
+    true
+}
+
 pub unsafe fn idle() {
     let mut buffer = [0_u8; 2];
     let mut num_read = 0;
@@ -4,7 +4,9 @@ use core::ptr;
 
 #[derive(macros::NoPanicErrorDebug)]
 pub enum Error {
+    CreateDirectory(u32),
     CreateFile,
+    SetFilePointer,
     WriteFile,
 }
 
@@ -57,6 +59,35 @@ impl File {
             }
         }
     }
+
+    pub fn rewind(&mut self) -> Result<(), Error> {
+        const FILE_BEGIN: u32 = 0;
+
+        let result =
+            unsafe { super::SetFilePointerEx(self.handle, 0, ptr::null_mut(), FILE_BEGIN) };
+
+        if result == 0 {
+            Err(Error::SetFilePointer)
+        } else {
+            Ok(())
+        }
+    }
+}
+
+// Creates the directory at the null-terminated `path`. Succeeds if the
+// directory already exists.
+pub unsafe fn create_directory<T: AsRef<[u8]>>(path: T) -> Result<(), Error> {
+    const ERROR_ALREADY_EXISTS: u32 = 183;
+
+    if super::CreateDirectoryA(path.as_ref().as_ptr(), ptr::null_mut()) == 0 {
+        let error = super::GetLastError();
+
+        if error != ERROR_ALREADY_EXISTS {
+            return Err(Error::CreateDirectory(error));
+        }
+    }
+
+    Ok(())
 }
 
 impl Drop for File {
@@ -47,7 +47,7 @@ impl Module {
             .find(|w| {
                 w.iter()
                     .zip(pattern)
-                    .all(|(&w, p)| p.map_or(true, |p| w == p))
+                    .all(|(&w, p)| p.is_none_or(|p| w == p))
             })
             .map(|w| w.as_ptr().cast())
     }
@@ -84,7 +84,7 @@ impl Module {
         &self,
         start: *mut u8,
         min_required_len: usize,
-    ) -> Option<&mut [u8]> {
+    ) -> Option<&'static mut [u8]> {
         let backward = self.backward_cave_search(start, min_required_len);
         let forward = self.forward_cave_search(start, min_required_len);
 
@@ -0,0 +1,186 @@
+// Compile-time switches for the hooks. Flip these and rebuild.
+//
+// Every gameplay cheat below only takes effect while `MASTER_ENABLE` is on
+// as well: the hooks check `enabled(CHEAT)`, never the cheat's own switch on
+// its own. Turning `MASTER_ENABLE` off leaves the DLL inert, apart from the
+// things that aren't cheats: `LOG_EQUIP`, the Delete key's lighting toggle and
+// the SDK tooling. Settings that only tune a cheat, like `AUTO_COLLECT_RADIUS`,
+// do nothing without it. Cheats whose switch isn't a bool are off at their
+// neutral value, e.g. `TIME_DILATION` at 1.
+//
+// The cheats from before `MASTER_ENABLE`, from `NO_RECOIL` to `CHEAT_MANAGER`,
+// are on, so that turning `MASTER_ENABLE` on gives the DLL's old behavior.
+// Every cheat added since is off until you turn it on as well.
+
+// Off by default, so that injecting a fresh build changes nothing until the
+// cheats you want are opted into here.
+pub const MASTER_ENABLE: bool = false;
+
+pub const fn enabled(cheat: bool) -> bool {
+    MASTER_ENABLE && cheat
+}
+
+// Zero the recoil of every weapon as it's equipped.
+pub const NO_RECOIL: bool = true;
+
+// Zero the spread of every hitscan weapon as it's equipped.
+pub const NO_SPREAD: bool = true;
+
+// Keep the reserve ammo (`AmmoCount`) of the weapon shown on the HUD at a full
+// clip's worth (`ClipSize`) or more, and the double drills' fuel topped up,
+// whenever its ammo changes.
+pub const REFILL_AMMO: bool = true;
+
+// Resupply a grenade whenever grenades are equipped.
+pub const GRENADE_RESUPPLY: bool = true;
+
+// Send each of our hits to the server three times instead of once.
+pub const MULTIPLY_HITS: bool = true;
+
+// Let the Insert key toggle whether the local player can take damage.
+pub const GOD_MODE_KEY: bool = true;
+
+// Enable the game's cheat manager and its console commands.
+pub const CHEAT_MANAGER: bool = true;
+
+// Pull resource chunks (Nitra, gold, minerals) within `AUTO_COLLECT_RADIUS`
+// onto the player so they get picked up. Only takes effect while we're the
+// host, since clients can't move replicated actors.
+pub const AUTO_COLLECT: bool = false;
+
+// Centimeters.
+pub const AUTO_COLLECT_RADIUS: f32 = 1500.0;
+
+// The scan walks every object, so only run it every this many frames.
+pub const AUTO_COLLECT_INTERVAL_FRAMES: u32 = 30;
+
+// Keep the local player's equipped heat-based weapon (e.g. the minigun or the
+// EPC) from ever overheating.
+pub const NO_OVERHEAT: bool = false;
+
+// Perks to swap into the local player's equipped perk slots when they spawn,
+// as full `PerkAsset` names, e.g.
+// "FloatPerkAsset /Game/GameElements/KPI/Perks/PERK_HoverBoots.PERK_HoverBoots".
+// Only applies while we're the host. Empty disables the hook. The original
+// perks are put back when the hooks unload.
+pub const PERKS: &[&str] = &[];
+
+// Keep the local player's weapons from swaying, so the crosshair stays put
+// while idle or moving. Spread and recoil are handled separately and always
+// off. The original sway is put back when the hooks unload.
+pub const NO_SWAY: bool = false;
+
+// Reload the local player's weapon as soon as its clip runs empty, with the
+// usual reload animation and time.
+pub const AUTO_RELOAD: bool = false;
+
+// Keep shots from taking ammo out of the clip (`ClipCount`), so the clip and
+// its count on the HUD stay where they were. `REFILL_AMMO` tops up the reserve
+// instead. The clip is only kept at what it held when first noticed, so reload
+// once to keep it full. Ammo is decided by the server, so this only does
+// anything while we're the host.
+pub const NO_AMMO_CONSUMPTION: bool = false;
+
+// Multiply the reserve ammo capacity (`AmmoDrivenWeapon::MaxAmmo`) of the local
+// player's weapons by this when they're equipped. 1 disables the hook. The
+// original capacities are put back when the hooks unload.
+pub const CARRY_CAPACITY_MULTIPLIER: i32 = 1;
+
+// AIM ASSIST IS CHEATING AGAINST YOUR TEAMMATES' FUN, NOT JUST THE GAME. Only
+// turn this on in solo games or with a team that agreed to it.
+//
+// While the local player fires a weapon, turn their view toward the nearest
+// enemy within `AIM_ASSIST_MAX_ANGLE` of the crosshair. Each frame closes
+// `AIM_ASSIST_STRENGTH` of the remaining angle, so the view eases onto the
+// target instead of snapping.
+pub const AIM_ASSIST: bool = false;
+
+// Degrees from the crosshair.
+pub const AIM_ASSIST_MAX_ANGLE: f32 = 15.0;
+
+// Fraction of the remaining angle to turn per frame, between 0 and 1.
+pub const AIM_ASSIST_STRENGTH: f32 = 0.2;
+
+// How often to look for a new target. Finding one walks every object.
+pub const AIM_ASSIST_INTERVAL_FRAMES: u32 = 10;
+
+// DEBUG ONLY. Completes every mission objective as soon as it exists, so that
+// other hooks can be tested without playing through a mission. This skips
+// whatever the objectives would have spawned or triggered, can leave the
+// mission in states the game never expects, and may break progression or
+// achievements for the save it's used with. Only does anything while we're
+// the host: completing objectives as a client would desync us from the server.
+pub const SKIP_OBJECTIVES: bool = false;
+
+// How often to look for incomplete objectives. Finding them walks every object.
+pub const SKIP_OBJECTIVES_INTERVAL_FRAMES: u32 = 60;
+
+// Slow enemy projectiles down to `SLOW_PROJECTILES_SCALE` times their launch
+// speed, so they're easy to dodge. Our own and the other players' projectiles
+// keep their speed. Only does anything while we're the host, since the server
+// is what moves projectiles.
+pub const SLOW_PROJECTILES: bool = false;
+
+// Between 0 and 1. 0 stops enemy projectiles in midair.
+pub const SLOW_PROJECTILES_SCALE: f32 = 0.25;
+
+// The scan walks every object, so only run it every this many frames. A
+// projectile flies at full speed until the next scan finds it.
+pub const SLOW_PROJECTILES_INTERVAL_FRAMES: u32 = 5;
+
+// Orbit the view around the local player instead of looking through their
+// eyes. The mouse turns the camera as usual. Only affects our own view. The
+// normal camera is put back when the hooks unload.
+pub const FREE_CAMERA: bool = false;
+
+// How far behind the player the free camera sits, in centimeters.
+pub const FREE_CAMERA_DISTANCE: f32 = 400.0;
+
+// Report every cosmetic (armor, headwear, beards, weapon skins, ...) as
+// unlocked, so that all of them can be picked in the character and weapon
+// customization menus. Ownership lives in the save game and nothing there is
+// changed, so unloading the hooks locks everything again. Whatever was
+// equipped stays equipped, though, and is saved like any other choice.
+//
+// What you equip is sent to the other players through
+// `CharacterVanityComponent::Server_SetEquippedVanity`, and they will see it.
+// As far as we know the server doesn't check it against your unlocks, but
+// that isn't verified.
+pub const UNLOCK_COSMETICS: bool = false;
+
+// Where `common::log!` messages go: `common::ConsoleLog`, `common::DebugLog`
+// for a debugger or DebugView, or `common::NoLog`. Not a cheat, so it doesn't
+// depend on `MASTER_ENABLE`.
+pub const LOG: &dyn common::Log = &common::ConsoleLog;
+
+// Log the object and class name of each item as it's equipped, to help map
+// in-game items to SDK types. Each item is logged once until another item is
+// equipped.
+pub const LOG_EQUIP: bool = false;
+
+// Don't send the server our hitscan hits on other players, so our bullets
+// pass through teammates without hurting them. Everyone but enemies is a
+// player and a teammate in this game, so only hits on `PlayerCharacter`s are
+// dropped and damage to enemies is untouched. This only filters what we send
+// as a client; the server still decides damage for everyone else.
+pub const NO_FRIENDLY_FIRE: bool = false;
+
+// After the local player takes damage, make them invulnerable for
+// `IFRAMES_SECONDS` so that back-to-back hits can't chain. A softer take on
+// the Insert key's god mode toggle. Damage is decided by the server, so this
+// only does anything while we're the host.
+pub const IFRAMES: bool = false;
+
+pub const IFRAMES_SECONDS: f64 = 1.0;
+
+// Heal the local player back to full health every frame. Unlike the Insert
+// key's god mode, damage still lands and the hit reactions still play; the
+// health just comes right back. Stops while the player is dead. Health is
+// decided by the server, so this only does anything while we're the host.
+pub const REGEN: bool = false;
+
+// DEBUG ONLY. Scale how fast the game runs, e.g. 0.25 to watch other hooks in
+// slow motion. Clamped to between 0.1 and 10. 1 disables the hook, and normal
+// speed is put back when the hooks unload. The server owns the world's time
+// dilation, so this only does anything while we're the host.
+pub const TIME_DILATION: f32 = 1.0;
@@ -17,14 +17,23 @@ static mut GET_ITEM_NAME: MaybeUninit<FNativeFuncPtr> = MaybeUninit::uninit();
 // static mut ON_FLARE: MaybeUninit<FNativeFuncPtr> = MaybeUninit::uninit();
 static mut ON_KEYPRESS_INSERT: MaybeUninit<FNativeFuncPtr> = MaybeUninit::uninit();
 static mut ON_KEYPRESS_DELETE: MaybeUninit<FNativeFuncPtr> = MaybeUninit::uninit();
+static mut ON_CAMERA_TICK: MaybeUninit<FNativeFuncPtr> = MaybeUninit::uninit();
+static mut IS_VANITY_UNLOCKED: MaybeUninit<FNativeFuncPtr> = MaybeUninit::uninit();
+static mut IS_SKIN_UNLOCKED: MaybeUninit<FNativeFuncPtr> = MaybeUninit::uninit();
 
-static mut AMMO_DRIVEN_WEAPON: *const UClass = ptr::null();
-static mut THROWN_GRENADE_ITEM: *const UClass = ptr::null();
-static mut DOUBLE_DRILL_ITEM: *const UClass = ptr::null();
-static mut HITSCAN_BASE_COMPONENT: *const UClass = ptr::null();
 static mut ZIP_LINE_ITEM: *const UClass = ptr::null();
 static mut GRAPPLING_HOOK_GUN: *const UClass = ptr::null();
 static mut OUTLINE_COMPONENT: *const UClass = ptr::null();
+static mut RESOURCE_CHUNK: *const UClass = ptr::null();
+static mut OVERHEAT_COMPONENT: *const UClass = ptr::null();
+static mut PLAYER_CHARACTER: *const UClass = ptr::null();
+static mut PERK_USAGE_COMPONENT: *const UClass = ptr::null();
+static mut ENEMY_PAWN: *const UClass = ptr::null();
+static mut HEALTH_COMPONENT: *const UClass = ptr::null();
+static mut OBJECTIVE: *const UClass = ptr::null();
+static mut PROJECTILE_MOVEMENT: *const UClass = ptr::null();
+
+static mut GAMEPLAY_STATICS: *mut UObject = ptr::null_mut();
 
 static mut SERVER_REGISTER_HIT: *mut UFunction = ptr::null_mut();
 static mut SERVER_REGISTER_HIT_MULTI: *mut UFunction = ptr::null_mut();
@@ -57,6 +66,9 @@ pub struct Hooks {
     // _on_flare: UFunctionHook,
     _on_keypress_insert: UFunctionHook,
     _on_keypress_delete: UFunctionHook,
+    _on_camera_tick: Option<UFunctionHook>,
+    _is_vanity_unlocked: Option<UFunctionHook>,
+    _is_skin_unlocked: Option<UFunctionHook>,
 }
 
 impl Hooks {
@@ -77,17 +89,31 @@ impl Hooks {
             // _on_flare: UFunctionHook::new("Function /Game/UI/MainOnscreenHUD/HUD_Flares.HUD_Flares_C.OnFlareCountChanged", ON_FLARE.as_mut_ptr(), user::my_on_flare)?,
             _on_keypress_insert: UFunctionHook::new("Function /Game/Character/BP_PlayerCharacter.BP_PlayerCharacter_C.InpActEvt_Insert_K2Node_InputKeyEvent", ON_KEYPRESS_INSERT.as_mut_ptr(), user::my_on_keypress_insert)?,
             _on_keypress_delete: UFunctionHook::new("Function /Game/Character/BP_PlayerCharacter.BP_PlayerCharacter_C.InpActEvt_Delete_K2Node_InputKeyEvent", ON_KEYPRESS_DELETE.as_mut_ptr(), user::my_on_keypress_delete)?,
+            _on_camera_tick: if crate::config::enabled(user::CAMERA_TICK_CHEATS) { Some(UFunctionHook::new("Function /Game/Character/Camera/BP_FSDCameraManager.BP_FSDCameraManager_C.ReceiveTick", ON_CAMERA_TICK.as_mut_ptr(), user::my_on_camera_tick)?) } else { None },
+            _is_vanity_unlocked: if crate::config::enabled(crate::config::UNLOCK_COSMETICS) { Some(UFunctionHook::new("Function /Script/FSD.VanityItem.IsUnlocked", IS_VANITY_UNLOCKED.as_mut_ptr(), user::my_is_vanity_unlocked)?) } else { None },
+            _is_skin_unlocked: if crate::config::enabled(crate::config::UNLOCK_COSMETICS) { Some(UFunctionHook::new("Function /Script/FSD.ItemSkin.IsUnlocked", IS_SKIN_UNLOCKED.as_mut_ptr(), user::my_is_skin_unlocked)?) } else { None },
         })
     }
 
+    // Only what the enabled cheats use is looked up, so that a game update
+    // that renames something only breaks loading for the cheats that need it.
+    // The rest stay null.
     unsafe fn find_statics() -> Result<(), Error> {
-        AMMO_DRIVEN_WEAPON = find("Class /Script/FSD.AmmoDrivenWeapon")?.cast();
-        THROWN_GRENADE_ITEM = find("Class /Script/FSD.ThrownGrenadeItem")?.cast();
-        DOUBLE_DRILL_ITEM = find("Class /Script/FSD.DoubleDrillItem")?.cast();
-        HITSCAN_BASE_COMPONENT = find("Class /Script/FSD.HitscanBaseComponent")?.cast();
+        use crate::config::{self, enabled};
+
         ZIP_LINE_ITEM = find("Class /Script/FSD.ZipLineItem")?.cast();
         GRAPPLING_HOOK_GUN = find("Class /Script/FSD.GrapplingHookGun")?.cast();
         OUTLINE_COMPONENT = find("Class /Script/FSD.OutlineComponent")?.cast();
+        RESOURCE_CHUNK = find_if(enabled(config::AUTO_COLLECT), "Class /Script/FSD.ResourceChunk")?.cast();
+        OVERHEAT_COMPONENT = find_if(enabled(config::NO_OVERHEAT), "Class /Script/FSD.OverHeatComponent")?.cast();
+        PLAYER_CHARACTER = find("Class /Script/FSD.PlayerCharacter")?.cast();
+        PERK_USAGE_COMPONENT = find_if(enabled(!config::PERKS.is_empty()), "Class /Script/FSD.PerkUsageComponent")?.cast();
+        ENEMY_PAWN = find_if(enabled(config::AIM_ASSIST || config::SLOW_PROJECTILES), "Class /Script/FSD.EnemyPawn")?.cast();
+        HEALTH_COMPONENT = find_if(enabled(config::AIM_ASSIST), "Class /Script/FSD.HealthComponentBase")?.cast();
+        OBJECTIVE = find_if(enabled(config::SKIP_OBJECTIVES), "Class /Script/FSD.Objective")?.cast();
+        PROJECTILE_MOVEMENT = find_if(enabled(config::SLOW_PROJECTILES), "Class /Script/Engine.ProjectileMovementComponent")?.cast();
+
+        GAMEPLAY_STATICS = find_if(enabled(config::TIME_DILATION != 1.0), "GameplayStatics /Script/Engine.Default__GameplayStatics")?;
 
         SERVER_REGISTER_HIT = find("Function /Script/FSD.HitscanComponent.Server_RegisterHit")?.cast();
         SERVER_REGISTER_HIT_MULTI = find("Function /Script/FSD.MultiHitscanComponent.Server_RegisterHit")?.cast();
@@ -142,3 +168,20 @@ impl Drop for UFunctionHook {
 unsafe fn find(s: &'static str) -> Result<*mut UObject, Error> {
     (*common::GUObjectArray).find(s).map_err(|_| Error::FindStatic(s))
 }
+
+// `find(s)` if `needed`, and null otherwise.
+unsafe fn find_if(needed: bool, s: &'static str) -> Result<*mut UObject, Error> {
+    if needed {
+        find(s)
+    } else {
+        Ok(ptr::null_mut())
+    }
+}
+
+// Every live instance of `class`, excluding class default objects. This walks
+// the whole object array, so callers shouldn't run it every frame.
+unsafe fn instances_of(class: *const UClass) -> impl Iterator<Item = *mut UObject> {
+    (*common::GUObjectArray)
+        .iter()
+        .filter(move |&object| !object.is_null() && !(*object).is_default_object() && (*object).is(class))
+}
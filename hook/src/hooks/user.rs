@@ -2,16 +2,29 @@ use common::win::random;
 use common::{self, EClassCastFlags, FFrame, List, UFunction, UObject};
 use core::ffi::c_void;
 use core::mem;
-use sdk::Engine::{Actor, LocalPlayer};
+use sdk::Engine::{Actor, LocalPlayer, Pawn, PlayerCameraManager};
 use sdk::FSD::{FSDCheatManager, FSDPlayerController, PlayerCharacter};
 
+mod aim;
+mod camera;
+mod iframes;
+mod math;
+mod objective;
 mod pawn;
+mod perk;
+mod projectile;
+mod regen;
+mod resource;
+mod time;
 mod weapon;
 
 mod render;
 
 pub static mut SEEN_FUNCTIONS: List<*mut UFunction, 4096> = List::new();
 
+// The local player's pawn as of the last camera tick.
+static mut LOCAL_PAWN: *mut Pawn = core::ptr::null_mut();
+
 pub struct OneTimeModifications;
 
 impl OneTimeModifications {
@@ -24,6 +37,12 @@ impl Drop for OneTimeModifications {
     fn drop(&mut self) {
         unsafe {
             render::restore_lighting();
+            camera::restore();
+            iframes::restore();
+            time::restore();
+            perk::restore();
+            weapon::restore_carry_capacity();
+            weapon::restore_sway();
         }
     }
 }
@@ -66,6 +85,10 @@ pub unsafe extern "C" fn my_process_remote_function_for_channel(
         crate::PROCESS_REMOTE_FUNCTION_FOR_CHANNEL,
     );
 
+    if weapon::is_friendly_fire(function, parms) {
+        return;
+    }
+
     if weapon::is_server_register_hit(function) {
         for _ in 0..2 {
             original(
@@ -114,10 +137,10 @@ pub unsafe extern "C" fn my_process_remote_function_for_channel(
 //     original(function, object, stack, result);
 // }
 
-pub unsafe extern "C" fn my_add_cheats(controller: *mut FSDPlayerController, _: bool) {
+pub unsafe extern "C" fn my_add_cheats(controller: *mut FSDPlayerController, force: bool) {
     type AddCheats = unsafe extern "C" fn(*mut FSDPlayerController, bool);
     let original = mem::transmute::<*const c_void, AddCheats>(crate::ADD_CHEATS);
-    original(controller, true);
+    original(controller, force || crate::config::enabled(crate::config::CHEAT_MANAGER));
 }
 
 pub unsafe extern "C" fn my_on_item_amount_changed(
@@ -134,10 +157,31 @@ pub unsafe extern "C" fn my_get_item_name(
     stack: *mut FFrame,
     result: *mut c_void,
 ) {
-    weapon::on_item_equipped(context.cast());
+    weapon::on_item_equipped(context.cast(), LOCAL_PAWN);
     (*super::GET_ITEM_NAME.as_ptr())(context, stack, result);
 }
 
+// The customization menus ask `FSD::VanityItem::IsUnlocked()` and
+// `FSD::ItemSkin::IsUnlocked()` whether each cosmetic can be picked. Both
+// return a bool. The original still runs so that it steps over the arguments.
+pub unsafe extern "C" fn my_is_vanity_unlocked(
+    context: *mut UObject,
+    stack: *mut FFrame,
+    result: *mut c_void,
+) {
+    (*super::IS_VANITY_UNLOCKED.as_ptr())(context, stack, result);
+    *result.cast::<bool>() = true;
+}
+
+pub unsafe extern "C" fn my_is_skin_unlocked(
+    context: *mut UObject,
+    stack: *mut FFrame,
+    result: *mut c_void,
+) {
+    (*super::IS_SKIN_UNLOCKED.as_ptr())(context, stack, result);
+    *result.cast::<bool>() = true;
+}
+
 // pub unsafe extern "C" fn my_on_flare(
 //     context: *mut UObject,
 //     stack: *mut FFrame,
@@ -155,9 +199,12 @@ pub unsafe extern "C" fn my_on_keypress_insert(
     stack: *mut FFrame,
     result: *mut c_void,
 ) {
-    let character = context.cast::<PlayerCharacter>();
-    let health = (*character).HealthComponent;
-    (*health).ToggleCanTakeDamage();
+    if crate::config::enabled(crate::config::GOD_MODE_KEY) {
+        let character = context.cast::<PlayerCharacter>();
+        let health = (*character).HealthComponent;
+        (*health).ToggleCanTakeDamage();
+    }
+
     (*super::ON_KEYPRESS_INSERT.as_ptr())(context, stack, result);
 }
 
@@ -183,6 +230,62 @@ pub unsafe extern "C" fn my_on_keypress_delete(
     (*super::ON_KEYPRESS_DELETE.as_ptr())(context, stack, result);
 }
 
+// The cheats that run from `my_on_camera_tick()`, or that need the
+// `LOCAL_PAWN` it keeps. The tick is only hooked if one of them is enabled.
+pub const CAMERA_TICK_CHEATS: bool = {
+    use crate::config;
+
+    config::FREE_CAMERA
+        || config::AUTO_COLLECT
+        || config::SKIP_OBJECTIVES
+        || config::SLOW_PROJECTILES
+        || config::TIME_DILATION != 1.0
+        || config::NO_OVERHEAT
+        || config::AUTO_RELOAD
+        || !config::PERKS.is_empty()
+        || config::IFRAMES
+        || config::REGEN
+        || config::AIM_ASSIST
+        || config::CARRY_CAPACITY_MULTIPLIER > 1
+        || config::NO_SWAY
+};
+
+// Ticks once per frame for the local player's camera, which makes it a
+// convenient place to run per-frame work. The context is the camera manager.
+pub unsafe extern "C" fn my_on_camera_tick(
+    context: *mut UObject,
+    stack: *mut FFrame,
+    result: *mut c_void,
+) {
+    let camera = context.cast::<PlayerCameraManager>();
+    let controller = (*camera).PCOwner;
+
+    camera::free_camera(camera);
+
+    if !controller.is_null() {
+        let pawn = (*controller).Pawn;
+        LOCAL_PAWN = pawn;
+
+        if !pawn.is_null() {
+            resource::auto_collect(pawn);
+            objective::skip(pawn);
+            projectile::slow();
+            time::dilate(pawn);
+
+            if (*pawn.cast::<UObject>()).is(super::PLAYER_CHARACTER) {
+                weapon::no_overheat(pawn.cast());
+                weapon::auto_reload(pawn.cast());
+                perk::on_tick(pawn.cast());
+                iframes::on_tick(pawn.cast());
+                regen::on_tick(pawn.cast());
+                aim::assist(camera, controller, pawn.cast());
+            }
+        }
+    }
+
+    (*super::ON_CAMERA_TICK.as_ptr())(context, stack, result);
+}
+
 #[allow(dead_code)]
 pub unsafe extern "C" fn my_post_actor_construction(actor: *mut Actor) {
     type PostActorConstruction = unsafe extern "C" fn(*mut Actor);
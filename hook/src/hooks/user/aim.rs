@@ -0,0 +1,140 @@
+use super::math::{abs, atan2, sqrt};
+use crate::config;
+use crate::hooks::{instances_of, ENEMY_PAWN, HEALTH_COMPONENT};
+use common::{ObjectHandle, UObject};
+use core::f32::consts::PI;
+use core::ptr;
+use sdk::CoreUObject::{Rotator, Vector};
+use sdk::Engine::{PlayerCameraManager, PlayerController};
+use sdk::FSD::{AmmoDrivenWeapon, EnemyPawn, HealthComponentBase, PlayerCharacter};
+
+static mut FRAMES_UNTIL_SCAN: u32 = 0;
+static mut TARGET: Option<ObjectHandle<EnemyPawn>> = None;
+
+// Reads the view from `PlayerCameraManager::GetCameraLocation()` and
+// `Controller::GetControlRotation()`, and turns it with
+// `Controller::SetControlRotation()`, which only changes where the local
+// player looks. The nearest enemy is the `FSD::EnemyPawn` with the smallest
+// angle from the crosshair, rescanned every `AIM_ASSIST_INTERVAL_FRAMES`.
+pub unsafe fn assist(
+    camera: *mut PlayerCameraManager,
+    controller: *mut PlayerController,
+    character: *mut PlayerCharacter,
+) {
+    if !config::enabled(config::AIM_ASSIST) || !is_firing(character) {
+        TARGET = None;
+        return;
+    }
+
+    let eye = (*camera).GetCameraLocation();
+    let view = (*controller).GetControlRotation();
+
+    if FRAMES_UNTIL_SCAN > 0 {
+        FRAMES_UNTIL_SCAN -= 1;
+    } else {
+        FRAMES_UNTIL_SCAN = config::AIM_ASSIST_INTERVAL_FRAMES;
+        let target = find_target(&eye, &view);
+        TARGET = if target.is_null() { None } else { Some(ObjectHandle::new(target)) };
+    }
+
+    // The target can die or be destroyed between scans.
+    let target = match TARGET {
+        Some(target) => target.get(),
+        None => return,
+    };
+
+    if target.is_null() || !is_alive(target) {
+        TARGET = None;
+        return;
+    }
+
+    let wanted = look_at(&eye, &(*target).K2_GetActorLocation());
+
+    let turned = Rotator {
+        Pitch: view.Pitch + normalize(wanted.Pitch - view.Pitch) * config::AIM_ASSIST_STRENGTH,
+        Yaw: view.Yaw + normalize(wanted.Yaw - view.Yaw) * config::AIM_ASSIST_STRENGTH,
+        Roll: view.Roll,
+    };
+
+    (*controller).SetControlRotation(turned);
+}
+
+unsafe fn is_firing(character: *mut PlayerCharacter) -> bool {
+    let inventory = (*character).InventoryComponent;
+
+    if inventory.is_null() {
+        return false;
+    }
+
+    let item = (*inventory).EquippedItem.cast::<UObject>();
+
+    AmmoDrivenWeapon::from_object(item).is_some_and(|weapon| (*weapon).IsFiring)
+}
+
+unsafe fn find_target(eye: &Vector, view: &Rotator) -> *mut EnemyPawn {
+    let mut best = ptr::null_mut();
+    let mut best_angle = config::AIM_ASSIST_MAX_ANGLE;
+
+    for enemy in instances_of(ENEMY_PAWN) {
+        let enemy = enemy.cast::<EnemyPawn>();
+
+        if !is_alive(enemy) {
+            continue;
+        }
+
+        let wanted = look_at(eye, &(*enemy).K2_GetActorLocation());
+        let angle = abs(normalize(wanted.Pitch - view.Pitch)).max(abs(normalize(wanted.Yaw - view.Yaw)));
+
+        if angle < best_angle {
+            best = enemy;
+            best_angle = angle;
+        }
+    }
+
+    best
+}
+
+// Enemies come with different kinds of health component, but all of them are
+// `FSD::HealthComponentBase`s, which is where `IsDead()` lives.
+unsafe fn is_alive(enemy: *mut EnemyPawn) -> bool {
+    let health = (*enemy).GetComponentByClass(HEALTH_COMPONENT.cast_mut().cast()).cast::<HealthComponentBase>();
+    !health.is_null() && !(*health).IsDead()
+}
+
+fn look_at(from: &Vector, to: &Vector) -> Rotator {
+    let x = to.X - from.X;
+    let y = to.Y - from.Y;
+    let z = to.Z - from.Z;
+
+    Rotator {
+        Pitch: degrees(atan2(z, sqrt(x * x + y * y))),
+        Yaw: degrees(atan2(y, x)),
+        Roll: 0.0,
+    }
+}
+
+// Wraps an angle difference into [-180, 180) so we turn the short way around.
+// A remainder rather than a loop, so a huge angle can't hang, and a non-finite
+// one doesn't turn at all.
+fn normalize(degrees: f32) -> f32 {
+    if !degrees.is_finite() {
+        return 0.0;
+    }
+
+    let mut wrapped = (degrees + 180.0) % 360.0;
+
+    if wrapped < 0.0 {
+        wrapped += 360.0;
+    }
+
+    // A tiny negative remainder plus 360 can round up to 360.
+    if wrapped >= 360.0 {
+        wrapped = 0.0;
+    }
+
+    wrapped - 180.0
+}
+
+fn degrees(radians: f32) -> f32 {
+    radians * (180.0 / PI)
+}
@@ -0,0 +1,57 @@
+use crate::config;
+use common::{FName, ObjectHandle};
+use sdk::Engine::PlayerCameraManager;
+
+// The camera manager we changed and what we overwrote, so `restore()` can undo
+// it. A new camera manager (e.g. after a level change) means the old one died
+// with its level and there's nothing to restore.
+static mut CAMERA: Option<ObjectHandle<PlayerCameraManager>> = None;
+static mut ORIGINAL_STYLE: Option<FName> = None;
+static mut ORIGINAL_DISTANCE: f32 = 0.0;
+
+static mut FREE_CAM: Option<FName> = None;
+
+// `PlayerCameraManager::CameraStyle` picks one of the engine's built-in camera
+// modes. "FreeCam" orbits the view target at `FreeCamDistance` along the
+// control rotation. The camera manager only exists on our machine, so this
+// never touches what others see.
+pub unsafe fn free_camera(camera: *mut PlayerCameraManager) {
+    if !config::enabled(config::FREE_CAMERA) || CAMERA.is_some_and(|c| c.get() == camera) {
+        return;
+    }
+
+    let free_cam = match FREE_CAM.or_else(|| FName::find("FreeCam")) {
+        Some(name) => name,
+        None => {
+            common::log!("camera::free_camera(): couldn't find the FreeCam name.");
+            return;
+        }
+    };
+
+    FREE_CAM = Some(free_cam);
+    CAMERA = Some(ObjectHandle::new(camera));
+    ORIGINAL_STYLE = Some((*camera).CameraStyle);
+    ORIGINAL_DISTANCE = (*camera).FreeCamDistance;
+
+    (*camera).CameraStyle = free_cam;
+    (*camera).FreeCamDistance = config::FREE_CAMERA_DISTANCE;
+}
+
+pub unsafe fn restore() {
+    let camera = match CAMERA.take() {
+        Some(camera) => camera.get(),
+        None => return,
+    };
+
+    let style = ORIGINAL_STYLE.take();
+
+    if camera.is_null() {
+        return;
+    }
+
+    if let Some(style) = style {
+        (*camera).CameraStyle = style;
+    }
+
+    (*camera).FreeCamDistance = ORIGINAL_DISTANCE;
+}
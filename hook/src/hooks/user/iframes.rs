@@ -0,0 +1,65 @@
+use crate::config;
+use common::{timer, ObjectHandle};
+use sdk::FSD::{HealthComponent, PlayerCharacter};
+
+// The health component we made invulnerable, and until when. Zero means no
+// window is open.
+static mut HEALTH: Option<ObjectHandle<HealthComponent>> = None;
+static mut LAST_HEALTH: f32 = 0.0;
+static mut INVULNERABLE_UNTIL: f64 = 0.0;
+
+// `HealthComponent::canTakeDamage` is the flag that the Insert key flips
+// through `ToggleCanTakeDamage()`. While it's false the component ignores all
+// damage. Rather than hook every way damage can arrive, we notice a hit by
+// `GetHealth()` dropping between frames, which costs a frame of latency.
+//
+// A window only opens while damage is on, so it never turns off a god mode
+// that the Insert key turned on.
+pub unsafe fn on_tick(character: *mut PlayerCharacter) {
+    if !config::enabled(config::IFRAMES) || !(*character).HasAuthority() {
+        return;
+    }
+
+    let health = (*character).HealthComponent;
+
+    if health.is_null() {
+        return;
+    }
+
+    // A new character (e.g. after respawning or a level change) comes with a
+    // new component, and the old one may be gone, so there's nothing to undo.
+    if !HEALTH.is_some_and(|h| h.get() == health) {
+        HEALTH = Some(ObjectHandle::new(health));
+        INVULNERABLE_UNTIL = 0.0;
+        LAST_HEALTH = (*health).GetHealth();
+        return;
+    }
+
+    let now = timer::now();
+
+    if INVULNERABLE_UNTIL > 0.0 && now >= INVULNERABLE_UNTIL {
+        (*health).canTakeDamage = true;
+        INVULNERABLE_UNTIL = 0.0;
+    }
+
+    let current = (*health).GetHealth();
+
+    if current < LAST_HEALTH && INVULNERABLE_UNTIL == 0.0 && (*health).canTakeDamage {
+        (*health).canTakeDamage = false;
+        INVULNERABLE_UNTIL = now + config::IFRAMES_SECONDS;
+    }
+
+    LAST_HEALTH = current;
+}
+
+pub unsafe fn restore() {
+    if let Some(health) = HEALTH.take() {
+        let health = health.get();
+
+        if !health.is_null() && INVULNERABLE_UNTIL > 0.0 {
+            (*health).canTakeDamage = true;
+        }
+    }
+
+    INVULNERABLE_UNTIL = 0.0;
+}
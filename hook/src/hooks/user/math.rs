@@ -0,0 +1,55 @@
+use core::f32::consts::PI;
+
+// `core` has no float math, so these are small stand-ins that are plenty
+// accurate for aiming and for scaling speeds.
+
+pub fn abs(x: f32) -> f32 {
+    if x < 0.0 {
+        -x
+    } else {
+        x
+    }
+}
+
+pub fn sqrt(x: f32) -> f32 {
+    if x <= 0.0 {
+        return 0.0;
+    }
+
+    let mut guess = if x > 1.0 { x } else { 1.0 };
+
+    for _ in 0..20 {
+        guess = 0.5 * (guess + x / guess);
+    }
+
+    guess
+}
+
+pub fn atan2(y: f32, x: f32) -> f32 {
+    if x == 0.0 && y == 0.0 {
+        return 0.0;
+    }
+
+    // atan(t) for t in [0, 1], accurate to about 2e-6 radians.
+    fn atan01(t: f32) -> f32 {
+        const COEFFICIENTS: [f32; 6] = [0.99997726, -0.33262347, 0.19354346, -0.11643287, 0.05265332, -0.0117212];
+        let t2 = t * t;
+        t * COEFFICIENTS.iter().rev().fold(0.0, |sum, &c| sum * t2 + c)
+    }
+
+    let (ax, ay) = (abs(x), abs(y));
+
+    let angle = if ax >= ay {
+        atan01(ay / ax)
+    } else {
+        PI / 2.0 - atan01(ax / ay)
+    };
+
+    let angle = if x < 0.0 { PI - angle } else { angle };
+
+    if y < 0.0 {
+        -angle
+    } else {
+        angle
+    }
+}
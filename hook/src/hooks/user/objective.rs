@@ -0,0 +1,37 @@
+use crate::config;
+use crate::hooks::{instances_of, OBJECTIVE};
+use sdk::Engine::Pawn;
+use sdk::FSD::Objective;
+
+static mut FRAMES_UNTIL_SCAN: u32 = 0;
+
+// Objectives are `FSD::Objective` components that the game mode creates on the
+// server for the current mission. `Objective::IsCompleted()` and
+// `Objective::CompleteObjective()` are the same calls the game makes once an
+// objective's goal is reached, so the server replicates the result as usual.
+//
+// A client also has replicated copies of the objectives, hence the authority
+// check: completing those would only change our copy.
+pub unsafe fn skip(pawn: *mut Pawn) {
+    if !config::enabled(config::SKIP_OBJECTIVES) || !(*pawn).HasAuthority() {
+        return;
+    }
+
+    if FRAMES_UNTIL_SCAN > 0 {
+        FRAMES_UNTIL_SCAN -= 1;
+        return;
+    }
+
+    FRAMES_UNTIL_SCAN = config::SKIP_OBJECTIVES_INTERVAL_FRAMES;
+
+    for objective in instances_of(OBJECTIVE) {
+        let objective = objective.cast::<Objective>();
+        let owner = (*objective).GetOwner();
+
+        if owner.is_null() || !(*owner).HasAuthority() || (*objective).IsCompleted() {
+            continue;
+        }
+
+        (*objective).CompleteObjective();
+    }
+}
@@ -0,0 +1,88 @@
+use crate::config;
+use crate::hooks::{instances_of, PERK_USAGE_COMPONENT};
+use common::{List, ObjectHandle, UObject};
+use core::ptr;
+use sdk::FSD::{PerkAsset, PerkUsageComponent, PlayerCharacter};
+
+const MAX_PERKS: usize = 8;
+
+// The character we last saw. A different one means the player (re)spawned.
+static mut CHARACTER: *mut PlayerCharacter = ptr::null_mut();
+
+// What we overwrote, so `restore()` can undo it.
+static mut COMPONENT: Option<ObjectHandle<PerkUsageComponent>> = None;
+static mut ORIGINAL_PERKS: List<*mut PerkAsset, MAX_PERKS> = List::new();
+
+// The equipped perks live in `FSD::PerkUsageComponent::Perks`, a
+// `TArray<*mut PerkAsset>` on the character. We overwrite the existing slots
+// in place rather than growing the array, since the engine owns its
+// allocation.
+pub unsafe fn on_tick(character: *mut PlayerCharacter) {
+    if !config::enabled(!config::PERKS.is_empty()) || character == CHARACTER {
+        return;
+    }
+
+    // The old character's component died with it; there's nothing to restore.
+    CHARACTER = character;
+    COMPONENT = None;
+    ORIGINAL_PERKS.clear();
+
+    // Perks are applied by the server. As a client we'd only change our HUD.
+    if !(*character).HasAuthority() {
+        return;
+    }
+
+    let component = instances_of(PERK_USAGE_COMPONENT)
+        .map(|c| c.cast::<PerkUsageComponent>())
+        .find(|&c| (*c).GetOwner().cast() == character);
+
+    if let Some(component) = component {
+        apply(component);
+    }
+}
+
+unsafe fn apply(component: *mut PerkUsageComponent) {
+    COMPONENT = Some(ObjectHandle::new(component));
+
+    for (slot, &name) in (*component).Perks.iter_mut().zip(config::PERKS.iter()) {
+        // Save every slot we visit, even ones we end up leaving alone, so that
+        // `ORIGINAL_PERKS` lines up with the array.
+        if ORIGINAL_PERKS.push(*slot).is_err() {
+            break;
+        }
+
+        let perk = match (*common::GUObjectArray).find(name) {
+            Ok(perk) => perk.cast::<PerkAsset>(),
+            Err(e) => {
+                common::log!("perk::apply(): {:?}", e);
+                continue;
+            }
+        };
+
+        common::log!("Perk: {} -> {}", display(*slot), display(perk));
+        *slot = perk;
+    }
+}
+
+pub unsafe fn restore() {
+    if let Some(component) = COMPONENT.take() {
+        let component = component.get();
+
+        // The component went away with its character, e.g. on a level change.
+        if !component.is_null() {
+            for (slot, &original) in (*component).Perks.iter_mut().zip(ORIGINAL_PERKS.iter()) {
+                *slot = original;
+            }
+        }
+    }
+
+    ORIGINAL_PERKS.clear();
+}
+
+unsafe fn display(perk: *mut PerkAsset) -> &'static str {
+    if perk.is_null() {
+        "(none)"
+    } else {
+        (*perk.cast::<UObject>()).name()
+    }
+}
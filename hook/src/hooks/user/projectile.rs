@@ -0,0 +1,70 @@
+use super::math::sqrt;
+use crate::config;
+use crate::hooks::{instances_of, ENEMY_PAWN, PROJECTILE_MOVEMENT};
+use common::UObject;
+use sdk::Engine::ProjectileMovementComponent;
+
+static mut FRAMES_UNTIL_SCAN: u32 = 0;
+
+// Projectiles (spitter acid, Menace and Warden shots, ...) are moved by the
+// engine's `ProjectileMovementComponent`, so we go through those rather than
+// FSD's projectile classes. A projectile is an enemy's if the actor that owns
+// the component was instigated by an `FSD::EnemyPawn`. Our own projectiles
+// have a player as their instigator and are left alone.
+//
+// Capping `MaxSpeed` and the current `Velocity` is idempotent, so a projectile
+// that's already been slowed is just slowed to the same speed again, and
+// there's nothing to remember or put back: projectiles only live for a few
+// seconds. The server moves projectiles and decides what they hit, so on a
+// client this would only slow our copy until the server corrects it, which is
+// why only the host's projectiles are touched.
+pub unsafe fn slow() {
+    if !config::enabled(config::SLOW_PROJECTILES) {
+        return;
+    }
+
+    if FRAMES_UNTIL_SCAN > 0 {
+        FRAMES_UNTIL_SCAN -= 1;
+        return;
+    }
+
+    FRAMES_UNTIL_SCAN = config::SLOW_PROJECTILES_INTERVAL_FRAMES;
+
+    let scale = config::SLOW_PROJECTILES_SCALE.clamp(0.0, 1.0);
+
+    for movement in instances_of(PROJECTILE_MOVEMENT) {
+        let movement = movement.cast::<ProjectileMovementComponent>();
+        let projectile = (*movement).GetOwner();
+
+        if projectile.is_null() || !(*projectile).HasAuthority() {
+            continue;
+        }
+
+        let instigator = (*projectile).GetInstigator();
+
+        if instigator.is_null() || !(*instigator.cast::<UObject>()).is(ENEMY_PAWN) {
+            continue;
+        }
+
+        // A `MaxSpeed` of 0 means no limit, so stopping a projectile keeps a
+        // crawl of 1 cm/s. Projectiles launched with an `InitialSpeed` of 0
+        // had their velocity set some other way, and we don't know what to
+        // scale.
+        if (*movement).InitialSpeed <= 0.0 {
+            continue;
+        }
+
+        let max_speed = ((*movement).InitialSpeed * scale).max(1.0);
+        (*movement).MaxSpeed = max_speed;
+
+        let velocity = &mut (*movement).Velocity;
+        let speed_squared = velocity.X * velocity.X + velocity.Y * velocity.Y + velocity.Z * velocity.Z;
+
+        if speed_squared > max_speed * max_speed {
+            let factor = max_speed / sqrt(speed_squared);
+            velocity.X *= factor;
+            velocity.Y *= factor;
+            velocity.Z *= factor;
+        }
+    }
+}
@@ -0,0 +1,26 @@
+use crate::config;
+use sdk::FSD::PlayerCharacter;
+
+// `HealthComponent::Damage` is how far below `GetMaxHealth()` the character's
+// health is, and `GetHealth()` is derived from it, so zeroing it heals in
+// full. The field replicates, which is why clients leave it alone.
+//
+// A downed or dead character keeps running its death sequence, and topping up
+// its health then would leave it alive but downed, or stuck in the animation.
+// So we stop at `IsDead()`. A hit that kills within a single frame still
+// kills, since we only heal between frames.
+pub unsafe fn on_tick(character: *mut PlayerCharacter) {
+    if !config::enabled(config::REGEN) || !(*character).HasAuthority() {
+        return;
+    }
+
+    let health = (*character).HealthComponent;
+
+    if health.is_null() || (*health).IsDead() {
+        return;
+    }
+
+    if (*health).GetHealth() < (*health).GetMaxHealth() {
+        (*health).Damage = 0.0;
+    }
+}
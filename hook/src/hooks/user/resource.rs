@@ -0,0 +1,49 @@
+use crate::config;
+use crate::hooks::{instances_of, RESOURCE_CHUNK};
+use sdk::CoreUObject::Vector;
+use sdk::Engine::Pawn;
+use sdk::FSD::ResourceChunk;
+
+static mut FRAMES_UNTIL_SCAN: u32 = 0;
+
+// Resource chunks are collected by touching them, so teleporting a chunk onto
+// the player is enough to pick it up. Uses `Actor::K2_GetActorLocation()`,
+// `Actor::K2_SetActorLocation()`, and `Actor::HasAuthority()` on
+// `FSD::ResourceChunk`, whose `CollectedBy` is set once someone grabs it.
+pub unsafe fn auto_collect(pawn: *mut Pawn) {
+    if !config::enabled(config::AUTO_COLLECT) {
+        return;
+    }
+
+    if FRAMES_UNTIL_SCAN > 0 {
+        FRAMES_UNTIL_SCAN -= 1;
+        return;
+    }
+
+    FRAMES_UNTIL_SCAN = config::AUTO_COLLECT_INTERVAL_FRAMES;
+
+    let player = (*pawn).K2_GetActorLocation();
+
+    for chunk in instances_of(RESOURCE_CHUNK) {
+        let chunk = chunk.cast::<ResourceChunk>();
+
+        // Moving an actor we don't have authority over only moves our copy of
+        // it, which the server will then correct.
+        if !(*chunk).CollectedBy.is_null() || !(*chunk).HasAuthority() {
+            continue;
+        }
+
+        let location = (*chunk).K2_GetActorLocation();
+
+        if distance_squared(&player, &location) <= config::AUTO_COLLECT_RADIUS * config::AUTO_COLLECT_RADIUS {
+            (*chunk).K2_SetActorLocation(player, false, true);
+        }
+    }
+}
+
+fn distance_squared(a: &Vector, b: &Vector) -> f32 {
+    let x = a.X - b.X;
+    let y = a.Y - b.Y;
+    let z = a.Z - b.Z;
+    x * x + y * y + z * z
+}
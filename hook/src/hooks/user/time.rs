@@ -0,0 +1,42 @@
+use crate::config;
+use crate::hooks::GAMEPLAY_STATICS;
+use common::ObjectHandle;
+use sdk::Engine::{GameplayStatics, Pawn};
+
+const MIN_DILATION: f32 = 0.1;
+const MAX_DILATION: f32 = 10.0;
+
+// A pawn in the world we changed, which `restore()` needs as the world
+// context. If the pawn has died with its level since, so has the dilation.
+static mut WORLD_CONTEXT: Option<ObjectHandle<Pawn>> = None;
+
+// `GameplayStatics::SetGlobalTimeDilation()` sets
+// `WorldSettings::TimeDilation`, which scales every tick in the world. The
+// engine then clamps it to the world settings' own
+// `MinGlobalTimeDilation`/`MaxGlobalTimeDilation`. Loading a level resets it,
+// so check every frame rather than setting it once. Time dilation replicates
+// from the server, which is why clients leave it alone.
+pub unsafe fn dilate(pawn: *mut Pawn) {
+    if !config::enabled(config::TIME_DILATION != 1.0) || !(*pawn).HasAuthority() {
+        return;
+    }
+
+    let statics = GAMEPLAY_STATICS.cast::<GameplayStatics>();
+    let dilation = config::TIME_DILATION.clamp(MIN_DILATION, MAX_DILATION);
+
+    if (*statics).GetGlobalTimeDilation(pawn.cast()) != dilation {
+        (*statics).SetGlobalTimeDilation(pawn.cast(), dilation);
+    }
+
+    WORLD_CONTEXT = Some(ObjectHandle::new(pawn));
+}
+
+pub unsafe fn restore() {
+    if let Some(pawn) = WORLD_CONTEXT.take() {
+        let pawn = pawn.get();
+
+        if !pawn.is_null() {
+            (*GAMEPLAY_STATICS.cast::<GameplayStatics>()).SetGlobalTimeDilation(pawn.cast(), 1.0);
+        }
+    }
+}
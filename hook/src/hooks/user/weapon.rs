@@ -1,51 +1,145 @@
-use common::UFunction;
-use sdk::FSD::{AmmoCountWidget, AmmoDrivenWeapon, DoubleDrillItem, HitscanBaseComponent, Item, RandRange, ThrownGrenadeItem};
+use crate::config;
+use common::{List, ObjectHandle, UFunction, UObject};
+use core::ffi::c_void;
+use core::ptr;
+use sdk::Engine::{HitResult, Pawn};
+use sdk::FSD::{AmmoCountWidget, AmmoDrivenWeapon, DoubleDrillItem, HitscanBaseComponent, Item, OverHeatComponent, PlayerCharacter, RandRange, ThrownGrenadeItem};
 
-pub unsafe fn on_item_amount_changed(widget: *mut AmmoCountWidget) {
-    use crate::hooks::*;
+// The item whose overheat component we last looked up, and that component (or
+// null if the item doesn't overheat). The component is owned by the item, so
+// it stays valid for as long as the same item is equipped.
+static mut HEAT_ITEM: *mut Item = ptr::null_mut();
+static mut HEAT_COMPONENT: *mut OverHeatComponent = ptr::null_mut();
+
+// Weapons whose `MaxAmmo` we raised, and what it was before.
+static mut RAISED_CAPACITIES: Tracked<i32, 16> = List::new();
+
+// The weapon we last asked to reload, so that each empty clip is only
+// reloaded once.
+static mut RELOADING: *mut AmmoDrivenWeapon = ptr::null_mut();
+
+// Weapons we stabilized, and their `SwayIntensity` and `SwaySpeed` before.
+static mut STABILIZED: Tracked<(f32, f32), 16> = List::new();
+
+// Weapons whose clip we keep from emptying, and what's in it. As the host
+// that's every player's weapons, not just ours.
+static mut FULL_CLIPS: Tracked<i32, 64> = List::new();
+
+// Weapons we changed, with something to remember about each. They're held by
+// `ObjectHandle`, since a weapon can be destroyed, e.g. on a level change, and
+// a new one take its address.
+type Tracked<T, const N: usize> = List<(ObjectHandle<AmmoDrivenWeapon>, T), N>;
+
+// The entry of `tracked` for `weapon`, if it's still alive.
+unsafe fn find<T, const N: usize>(tracked: &mut Tracked<T, N>, weapon: *mut AmmoDrivenWeapon) -> Option<&mut T> {
+    tracked
+        .as_mut_slice()
+        .iter_mut()
+        .find(|(w, _)| w.get() == weapon)
+        .map(|(_, value)| value)
+}
+
+// Adds `weapon` to `tracked`, first forgetting weapons that have been
+// destroyed. Returns whether there was room.
+unsafe fn track<T, const N: usize>(tracked: &mut Tracked<T, N>, weapon: *mut AmmoDrivenWeapon, value: T) -> bool {
+    static mut WARNED: bool = false;
+
+    let mut i = 0;
+
+    while i < tracked.len() {
+        if tracked.get_unchecked(i).0.get().is_null() {
+            let _ = tracked.swap_remove(i);
+        } else {
+            i += 1;
+        }
+    }
+
+    if tracked.push((ObjectHandle::new(weapon), value)).is_ok() {
+        true
+    } else {
+        if !WARNED {
+            WARNED = true;
+            common::log!("warning: already tracking {} weapons, leaving {} as is.", N, *weapon);
+        }
 
+        false
+    }
+}
+
+pub unsafe fn on_item_amount_changed(widget: *mut AmmoCountWidget) {
     let item = (*widget).Item.cast::<UObject>();
 
-    if (*item).is(AMMO_DRIVEN_WEAPON) {
-        let weapon = item.cast::<AmmoDrivenWeapon>();
+    if let Some(weapon) = AmmoDrivenWeapon::from_object(item) {
+        keep_clip(weapon);
+    }
+
+    if !config::enabled(config::REFILL_AMMO) {
+        return;
+    }
 
+    if let Some(weapon) = AmmoDrivenWeapon::from_object(item) {
         if (*weapon).AmmoCount < (*weapon).ClipSize {
             (*weapon).AmmoCount = (*weapon).ClipSize;
         }
-    } else if (*item).is(DOUBLE_DRILL_ITEM) {
-        let drill = item.cast::<DoubleDrillItem>();
-
+    } else if let Some(drill) = DoubleDrillItem::from_object(item) {
         if (*drill).Fuel < 2.0 {
             (*drill).Fuel = 2.0;
         }
     }
 }
 
-pub unsafe fn on_item_equipped(item: *mut Item) {
-    use crate::hooks::*;
-
+pub unsafe fn on_item_equipped(item: *mut Item, local_pawn: *mut Pawn) {
     if item.is_null() {
         return;
     }
-    
+
+    let is_local = !local_pawn.is_null() && (*item).Owner.cast() == local_pawn;
     let item = item.cast::<UObject>();
 
-    if (*item).is(AMMO_DRIVEN_WEAPON) {
-        let weapon = item.cast();
+    if config::LOG_EQUIP {
+        log_equipped(item);
+    }
+
+    if let Some(weapon) = AmmoDrivenWeapon::from_object(item) {
+        if config::enabled(config::NO_RECOIL) {
+            no_recoil(weapon);
+        }
 
-        no_recoil(weapon);
+        if is_local {
+            raise_carry_capacity(weapon);
+            stabilize_aim(weapon);
+        }
 
-        let fire = (*weapon).WeaponFire.cast::<UObject>();
-        
-        if !fire.is_null() && (*fire).is(HITSCAN_BASE_COMPONENT) {
-            no_spread(fire.cast());
+        if config::enabled(config::NO_SPREAD) {
+            if let Some(hitscan) = HitscanBaseComponent::from_object((*weapon).WeaponFire.cast()) {
+                no_spread(hitscan);
+            }
+        }
+    } else if let Some(grenade) = ThrownGrenadeItem::from_object(item) {
+        if config::enabled(config::GRENADE_RESUPPLY) {
+            (*grenade).Server_Resupply(1.0);
         }
-    } else if (*item).is(THROWN_GRENADE_ITEM) {
-        let item = item.cast::<ThrownGrenadeItem>();
-        (*item).Server_Resupply(1.0);
     }
 }
 
+// `on_item_equipped()` runs whenever the game asks for an item's name, which
+// can be many times a second, so only log when the item changes.
+unsafe fn log_equipped(item: *mut UObject) {
+    static mut LAST_LOGGED: *mut UObject = ptr::null_mut();
+
+    if item == LAST_LOGGED {
+        return;
+    }
+
+    LAST_LOGGED = item;
+
+    common::log!(
+        "Equipped {} ({})",
+        (*item).name(),
+        (*(*item).class().cast::<UObject>()).name(),
+    );
+}
+
 pub unsafe fn no_spread(hitscan: *mut HitscanBaseComponent) {
     (*hitscan).SpreadPerShot = 0.0;
     (*hitscan).MinSpread = 0.0;
@@ -58,6 +152,98 @@ pub unsafe fn no_spread(hitscan: *mut HitscanBaseComponent) {
     (*hitscan).MaxHorizontalSpread = 0.0;
 }
 
+unsafe fn raise_carry_capacity(weapon: *mut AmmoDrivenWeapon) {
+    if !config::enabled(config::CARRY_CAPACITY_MULTIPLIER > 1) {
+        return;
+    }
+
+    let raised = &mut *ptr::addr_of_mut!(RAISED_CAPACITIES);
+
+    if find(raised, weapon).is_some() {
+        return;
+    }
+
+    let original = (*weapon).MaxAmmo;
+
+    if track(raised, weapon, original) {
+        (*weapon).MaxAmmo = original.saturating_mul(config::CARRY_CAPACITY_MULTIPLIER);
+    }
+}
+
+pub unsafe fn restore_carry_capacity() {
+    let raised = &mut *ptr::addr_of_mut!(RAISED_CAPACITIES);
+
+    for &(weapon, original) in raised.iter() {
+        let weapon = weapon.get();
+
+        if !weapon.is_null() {
+            (*weapon).MaxAmmo = original;
+        }
+    }
+
+    raised.clear();
+}
+
+// Sway is the idle and movement drift of the first-person weapon, on top of
+// spread and recoil. `AmmoDrivenWeapon::SwayIntensity` scales how far the
+// view drifts and `AmmoDrivenWeapon::SwaySpeed` how fast; zeroing both holds
+// the crosshair still. Both only feed our local view.
+//
+// Some weapons may drive sway from fields of their own subclass instead. If
+// one still sways, look for the sway fields of its class in the generated SDK.
+unsafe fn stabilize_aim(weapon: *mut AmmoDrivenWeapon) {
+    let stabilized = &mut *ptr::addr_of_mut!(STABILIZED);
+
+    if !config::enabled(config::NO_SWAY) || find(stabilized, weapon).is_some() {
+        return;
+    }
+
+    if track(stabilized, weapon, ((*weapon).SwayIntensity, (*weapon).SwaySpeed)) {
+        (*weapon).SwayIntensity = 0.0;
+        (*weapon).SwaySpeed = 0.0;
+    }
+}
+
+pub unsafe fn restore_sway() {
+    let stabilized = &mut *ptr::addr_of_mut!(STABILIZED);
+
+    for &(weapon, (intensity, speed)) in stabilized.iter() {
+        let weapon = weapon.get();
+
+        if !weapon.is_null() {
+            (*weapon).SwayIntensity = intensity;
+            (*weapon).SwaySpeed = speed;
+        }
+    }
+
+    stabilized.clear();
+}
+
+// `AmmoCountWidget::OnItemAmountChanged()` is the HUD's ammo counter hearing
+// that the weapon's ammo changed, which happens right after each shot takes
+// its round out of `ClipCount`. Putting the round back there, before the
+// counter reads it, keeps both the clip and the HUD full. A reload raises
+// `ClipCount`, so every rise is taken as the new count to keep.
+//
+// `ClipCount` replicates from the server, which is why clients leave it alone.
+unsafe fn keep_clip(weapon: *mut AmmoDrivenWeapon) {
+    if !config::enabled(config::NO_AMMO_CONSUMPTION) || !(*weapon).HasAuthority() {
+        return;
+    }
+
+    let clip = (*weapon).ClipCount;
+    let full_clips = &mut *ptr::addr_of_mut!(FULL_CLIPS);
+
+    match find(full_clips, weapon) {
+        Some(kept) if clip < *kept => (*weapon).ClipCount = *kept,
+        Some(kept) => *kept = clip,
+        None => {
+            // Once the list is full, further weapons consume ammo as usual.
+            track(full_clips, weapon, clip);
+        }
+    }
+}
+
 pub unsafe fn no_recoil(weapon: *mut AmmoDrivenWeapon) {
     const ZERO: RandRange = RandRange { Min: 0.0, Max: 0.0 };
     (*weapon).RecoilSettings.RecoilRoll = ZERO;
@@ -65,8 +251,104 @@ pub unsafe fn no_recoil(weapon: *mut AmmoDrivenWeapon) {
     (*weapon).RecoilSettings.RecoilYaw = ZERO;
 }
 
+// `AmmoDrivenWeapon::Server_Reload()` is the RPC behind the reload key. Going
+// through it plays the normal reload, and as a client the server still decides
+// whether the reload happens. `ClipCount` is the ammo in the clip and
+// `AmmoCount` what's left in reserve.
+pub unsafe fn auto_reload(character: *mut PlayerCharacter) {
+    if !config::enabled(config::AUTO_RELOAD) {
+        return;
+    }
+
+    let inventory = (*character).InventoryComponent;
+
+    if inventory.is_null() {
+        return;
+    }
+
+    let weapon = match AmmoDrivenWeapon::from_object((*inventory).EquippedItem.cast()) {
+        Some(weapon) => weapon,
+        None => return,
+    };
+
+    if (*weapon).ClipCount > 0 {
+        if weapon == RELOADING {
+            RELOADING = ptr::null_mut();
+        }
+    } else if (*weapon).AmmoCount > 0 && weapon != RELOADING {
+        RELOADING = weapon;
+        (*weapon).Server_Reload();
+    }
+}
+
+// Heat lives on an `FSD::OverHeatComponent` owned by the weapon rather than on
+// the weapon itself. Finding it walks the object array, so only do that when
+// the equipped item changes, then pin `OverHeatComponent::Heat` to zero every
+// tick.
+pub unsafe fn no_overheat(character: *mut PlayerCharacter) {
+    use crate::hooks::*;
+
+    if !config::enabled(config::NO_OVERHEAT) {
+        return;
+    }
+
+    let inventory = (*character).InventoryComponent;
+
+    if inventory.is_null() {
+        return;
+    }
+
+    let item = (*inventory).EquippedItem;
+
+    if item.is_null() {
+        return;
+    }
+
+    if item != HEAT_ITEM {
+        HEAT_ITEM = item;
+        HEAT_COMPONENT = instances_of(OVERHEAT_COMPONENT)
+            .map(|c| c.cast::<OverHeatComponent>())
+            .find(|&c| (*c).GetOwner().cast() == item)
+            .unwrap_or(ptr::null_mut());
+    }
+
+    if !HEAT_COMPONENT.is_null() {
+        (*HEAT_COMPONENT).Heat = 0.0;
+    }
+}
+
+// The start of `FSD::HitscanComponent::Server_RegisterHit()`'s parameters.
+#[repr(C)]
+struct RegisterHitParameters {
+    Hit: HitResult,
+}
+
+// Whether `function` is one of our hits on another player about to be sent to
+// the server. Only single hits through `Server_RegisterHit()` and
+// `Server_RegisterRicochetHit()` are checked, which both start with the
+// `FHitResult` of the hit; `HitResult::Actor` is what got hit.
+pub unsafe fn is_friendly_fire(function: *mut UFunction, parameters: *mut c_void) -> bool {
+    use crate::hooks::*;
+
+    if !config::enabled(config::NO_FRIENDLY_FIRE)
+        || parameters.is_null()
+        || (function != SERVER_REGISTER_HIT && function != SERVER_REGISTER_RICOCHET_HIT)
+    {
+        return false;
+    }
+
+    let victim = (*parameters.cast::<RegisterHitParameters>()).Hit.Actor.get().cast::<UObject>();
+
+    !victim.is_null() && (*victim).is(PLAYER_CHARACTER)
+}
+
 pub unsafe fn is_server_register_hit(function: *mut UFunction) -> bool {
     use crate::hooks::*;
+
+    if !config::enabled(config::MULTIPLY_HITS) {
+        return false;
+    }
+
     function == SERVER_REGISTER_HIT || 
     function == SERVER_REGISTER_HIT_MULTI ||
     function == SERVER_REGISTER_HIT_TERRAIN ||
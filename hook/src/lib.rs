@@ -15,6 +15,8 @@ use core::ffi::c_void;
 use core::ptr;
 use sdk::Engine::Engine;
 
+mod config;
+
 mod hooks;
 use hooks::Hooks;
 
@@ -47,6 +49,9 @@ unsafe extern "system" fn _DllMainCRTStartup(dll: *mut c_void, reason: u32, _: *
 
 unsafe extern "system" fn on_attach(dll: *mut c_void) -> u32 {
     win::AllocConsole();
+    common::set_logger(config::LOG);
+
+    common::timer::initialize_ticks_per_second();
 
     if let Err(e) = run() {
         common::log!("error: {:?}", e);
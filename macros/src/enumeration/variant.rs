@@ -97,8 +97,7 @@ impl Variant {
                 None
             })
             .filter(|attribute| attribute.delimiter() == Delimiter::Bracket)
-            .filter(|attribute| matches!(attribute.stream().into_iter().next(), Some(TokenTree::Ident(ident)) if ident.to_string() == "from"))
-            .is_some();
+            .is_some_and(|attribute| matches!(attribute.stream().into_iter().next(), Some(TokenTree::Ident(ident)) if ident.to_string() == "from"));
 
         assert!(has_from_attribute, "expected #[from] attribute for variant {name}");
 
@@ -1,40 +1,81 @@
 use common::List;
+use core::borrow::BorrowMut;
 use core::fmt::{self, Write};
 use core::str;
 
-pub struct BufWriter<W: Write> {
+// `B` is either the buffer itself or a `&mut` to one that lives elsewhere, e.g.
+// in a static when `N` is too big for the stack.
+pub struct BufWriter<W: Write, const N: usize = 8192, B: BorrowMut<List<u8, N>> = List<u8, N>> {
     writer: W,
-    buffer: List<u8, 8192>,
+    buffer: B,
+    writes: usize,
 }
 
 impl<W: Write> BufWriter<W> {
     pub fn new(writer: W) -> BufWriter<W> {
+        BufWriter::sized(writer)
+    }
+}
+
+impl<W: Write, const N: usize> BufWriter<W, N> {
+    // Buffers `N` bytes at a time instead of the default 8 KiB.
+    pub fn sized(writer: W) -> BufWriter<W, N> {
         BufWriter {
             writer,
             buffer: List::new(),
+            writes: 0,
+        }
+    }
+}
+
+impl<W: Write, const N: usize> BufWriter<W, N, &'static mut List<u8, N>> {
+    // Buffers into `buffer` instead of a `List` of its own. Whatever `buffer`
+    // held before is discarded.
+    pub fn with_buffer(writer: W, buffer: &'static mut List<u8, N>) -> Self {
+        buffer.clear();
+
+        BufWriter {
+            writer,
+            buffer,
+            writes: 0,
         }
     }
+}
+
+impl<W: Write, const N: usize, B: BorrowMut<List<u8, N>>> BufWriter<W, N, B> {
+    pub fn flush(&mut self) -> Result<(), fmt::Error> {
+        let buffer = self.buffer.borrow_mut();
 
-    fn flush(&mut self) -> Result<(), fmt::Error> {
-        let s = unsafe { str::from_utf8_unchecked(self.buffer.as_slice()) };
+        if buffer.is_empty() {
+            return Ok(());
+        }
+
+        let s = unsafe { str::from_utf8_unchecked(buffer.as_slice()) };
         self.writer.write_str(s)?;
-        self.buffer.clear();
+        buffer.clear();
+        self.writes += 1;
         Ok(())
     }
+
+    // How many times the buffer was written out to the underlying writer,
+    // i.e. one `WriteFile` each when writing to a `File`.
+    pub fn writes(&self) -> usize {
+        self.writes
+    }
 }
 
-impl<W: Write> Write for BufWriter<W> {
+impl<W: Write, const N: usize, B: BorrowMut<List<u8, N>>> Write for BufWriter<W, N, B> {
     fn write_str(&mut self, s: &str) -> Result<(), fmt::Error> {
         let s = s.as_bytes();
         let mut cursor = 0;
 
         while cursor < s.len() {
-            let mut space_left_in_buffer = self.buffer.capacity() - self.buffer.len();
+            let mut space_left_in_buffer = N - self.buffer.borrow().len();
 
             // Flush if buffer is full.
             if space_left_in_buffer == 0 {
                 self.flush()?;
-                space_left_in_buffer = self.buffer.capacity();
+                space_left_in_buffer = N;
             }
 
             // Write min(space left in buffer, string bytes left to write) bytes to buffer.
@@ -55,7 +96,7 @@ impl<W: Write> Write for BufWriter<W> {
                 s.get_unchecked(cursor..cursor + num_bytes_to_write_now)
             };
 
-            self.buffer.write_bytes(piece).map_err(|_| fmt::Error)?;
+            self.buffer.borrow_mut().write_bytes(piece).map_err(|_| fmt::Error)?;
 
             // Advance cursor to next position we'll read from.
             cursor += num_bytes_to_write_now;
@@ -65,8 +106,34 @@ impl<W: Write> Write for BufWriter<W> {
     }
 }
 
-impl<W: Write> Drop for BufWriter<W> {
+impl<W: Write, const N: usize, B: BorrowMut<List<u8, N>>> Drop for BufWriter<W, N, B> {
     fn drop(&mut self) {
         let _ = self.flush();
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_one_write_per_flush() {
+        static mut BUFFER: List<u8, 4> = List::new();
+        let mut out = List::<u8, 16>::new();
+
+        {
+            let mut writer = BufWriter::with_buffer(&mut out, unsafe { &mut *core::ptr::addr_of_mut!(BUFFER) });
+            writer.write_str("hello, ").unwrap();
+            writer.write_str("world").unwrap();
+            assert_eq!(writer.writes(), 2);
+            writer.flush().unwrap();
+            assert_eq!(writer.writes(), 3);
+
+            // Nothing left to write.
+            writer.flush().unwrap();
+            assert_eq!(writer.writes(), 3);
+        }
+
+        assert_eq!(out.as_slice(), b"hello, world");
+    }
+}
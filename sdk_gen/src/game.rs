@@ -1,12 +1,16 @@
 #![allow(non_snake_case, non_upper_case_globals, non_camel_case_types)]
 
+use crate::generator::imports;
+
 use core::fmt::{self, Display, Formatter};
 
 use common::{
-    impl_deref, EClassCastFlags, FField, FName, FString, TArray, UClass, UField, UObject, UPackage,
-    UStruct,
+    impl_deref, EClassCastFlags, EPropertyFlags, FField, FName, FString, TArray, UClass, UField,
+    UObject, UPackage, UStruct,
 };
 
+pub use common::TPair;
+
 #[derive(macros::NoPanicErrorDebug)]
 pub enum Error {
     Fmt(#[from] fmt::Error),
@@ -23,74 +27,13 @@ pub struct FProperty {
     pad1: [u8; 40],
 }
 
-#[derive(Copy, Clone)]
-#[repr(transparent)]
-pub struct EPropertyFlags(pub u64);
-
-#[allow(dead_code)]
-impl EPropertyFlags {
-    // Engine\Source\Runtime\CoreUObject\Public\UObject\ObjectMacros.h
-    pub const CPF_None: Self = Self(0);
-    pub const CPF_Edit: Self = Self(0x1); // < Property is user-settable in the editor.
-    pub const CPF_ConstParm: Self = Self(0x2); // < This is a constant function parameter
-    pub const CPF_BlueprintVisible: Self = Self(0x4); // < This property can be read by blueprint code
-    pub const CPF_ExportObject: Self = Self(0x8); // < Object can be exported with actor.
-    pub const CPF_BlueprintReadOnly: Self = Self(0x10); // < This property cannot be modified by blueprint code
-    pub const CPF_Net: Self = Self(0x20); // < Property is relevant to network replication.
-    pub const CPF_EditFixedSize: Self = Self(0x40); // < Indicates that elements of an array can be modified, but its size cannot be changed.
-    pub const CPF_Parm: Self = Self(0x80); // < Function/When call parameter.
-    pub const CPF_OutParm: Self = Self(0x100); // < Value is copied out after function call.
-    pub const CPF_ZeroConstructor: Self = Self(0x200); // < memset is fine for construction
-    pub const CPF_ReturnParm: Self = Self(0x400); // < Return value.
-    pub const CPF_DisableEditOnTemplate: Self = Self(0x800); // < Disable editing of this property on an archetype/sub-blueprint
-    pub const CPF_Transient: Self = Self(0x2000); // < Property is transient: shouldn't be saved or loaded, except for Blueprint CDOs.
-    pub const CPF_Config: Self = Self(0x4000); // < Property should be loaded/saved as permanent profile.
-    pub const CPF_DisableEditOnInstance: Self = Self(0x10000); // < Disable editing on an instance of this class
-    pub const CPF_EditConst: Self = Self(0x20000); // < Property is uneditable in the editor.
-    pub const CPF_GlobalConfig: Self = Self(0x40000); // < Load config from base class, not subclass.
-    pub const CPF_InstancedReference: Self = Self(0x80000); // < Property is a component references.
-    pub const CPF_DuplicateTransient: Self = Self(0x200000); // < Property should always be reset to the default value during any type of duplication (copy/paste, binary duplication, etc.)
-    pub const CPF_SubobjectReference: Self = Self(0x400000); // < Property contains subobject references (TSubobjectPtr)
-    pub const CPF_SaveGame: Self = Self(0x1000000); // < Property should be serialized for save games, this is only checked for game-specific archives with ArIsSaveGame
-    pub const CPF_NoClear: Self = Self(0x2000000); // < Hide clear (and browse) button.
-    pub const CPF_ReferenceParm: Self = Self(0x8000000); // < Value is passed by reference; CPF_OutParam and CPF_Param should also be set.
-    pub const CPF_BlueprintAssignable: Self = Self(0x10000000); // < MC Delegates only.  Property should be exposed for assigning in blueprint code
-    pub const CPF_Deprecated: Self = Self(0x20000000); // < Property is deprecated.  Read it from an archive, but don't save it.
-    pub const CPF_IsPlainOldData: Self = Self(0x40000000); // < If this is set, then the property can be memcopied instead of CopyCompleteValue / CopySingleValue
-    pub const CPF_RepSkip: Self = Self(0x80000000); // < Not replicated. For non replicated properties in replicated structs
-    pub const CPF_RepNotify: Self = Self(0x100000000); // < Notify actors when a property is replicated
-    pub const CPF_Interp: Self = Self(0x200000000); // < interpolatable property for use with matinee
-    pub const CPF_NonTransactional: Self = Self(0x400000000); // < Property isn't transacted
-    pub const CPF_EditorOnly: Self = Self(0x800000000); // < Property should only be loaded in the editor
-    pub const CPF_NoDestructor: Self = Self(0x1000000000); // < No destructor
-    pub const CPF_AutoWeak: Self = Self(0x4000000000); // < Only used for weak pointers, means the export type is autoweak
-    pub const CPF_ContainsInstancedReference: Self = Self(0x8000000000); // < Property contains component references.
-    pub const CPF_AssetRegistrySearchable: Self = Self(0x10000000000); // < asset instances will add properties with this flag to the asset registry automatically
-    pub const CPF_SimpleDisplay: Self = Self(0x20000000000); // < The property is visible by default in the editor details view
-    pub const CPF_AdvancedDisplay: Self = Self(0x40000000000); // < The property is advanced and not visible by default in the editor details view
-    pub const CPF_Protected: Self = Self(0x80000000000); // < property is protected from the perspective of script
-    pub const CPF_BlueprintCallable: Self = Self(0x100000000000); // < MC Delegates only.  Property should be exposed for calling in blueprint code
-    pub const CPF_BlueprintAuthorityOnly: Self = Self(0x200000000000); // < MC Delegates only.  This delegate accepts (only in blueprint) only events with BlueprintAuthorityOnly.
-    pub const CPF_TextExportTransient: Self = Self(0x400000000000); // < Property shouldn't be exported to text format (e.g. copy/paste)
-    pub const CPF_NonPIEDuplicateTransient: Self = Self(0x800000000000); // < Property should only be copied in PIE
-    pub const CPF_ExposeOnSpawn: Self = Self(0x1000000000000); // < Property is exposed on spawn
-    pub const CPF_PersistentInstance: Self = Self(0x2000000000000); // < A object referenced by the property is duplicated like a component. (Each actor should have an own instance.)
-    pub const CPF_UObjectWrapper: Self = Self(0x4000000000000); // < Property was parsed as a wrapper class like TSubclassOf<T>, FScriptInterface etc., rather than a USomething*
-    pub const CPF_HasGetValueTypeHash: Self = Self(0x8000000000000); // < This property can generate a meaningful hash value.
-    pub const CPF_NativeAccessSpecifierPublic: Self = Self(0x10000000000000); // < Public native access specifier
-    pub const CPF_NativeAccessSpecifierProtected: Self = Self(0x20000000000000); // < Protected native access specifier
-    pub const CPF_NativeAccessSpecifierPrivate: Self = Self(0x40000000000000); // < Private native access specifier
-    pub const CPF_SkipSerialization: Self = Self(0x80000000000000); // < Property shouldn't be serialized, can still be exported to text
-
-    pub fn contains(&self, flag: Self) -> bool {
-        self.0 & flag.0 == flag.0
-    }
-}
-
 pub struct PropertyDisplayable {
     property: *const FProperty,
     package: *const UPackage,
     is_struct_blueprint_generated: bool,
+    wrap_pointers: bool,
+    element: bool,
+    shorten_paths: bool,
 }
 
 impl PropertyDisplayable {
@@ -103,10 +46,103 @@ impl PropertyDisplayable {
             property,
             package,
             is_struct_blueprint_generated,
+            wrap_pointers: false,
+            element: false,
+            shorten_paths: false,
+        }
+    }
+
+    // Name types from other packages by their short name where
+    // `generator::imports` can import them into this package's module.
+    pub fn shorten_paths(mut self, shorten_paths: bool) -> Self {
+        self.shorten_paths = shorten_paths;
+        self
+    }
+
+    unsafe fn can_shorten(&self, typ: *const UObject) -> bool {
+        self.shorten_paths
+            && !self.is_struct_blueprint_generated
+            && imports::try_import(self.package, typ)
+    }
+
+    // Display the type of one element of a fixed-size array property, i.e.
+    // `T` rather than `[T; ArrayDim]`.
+    pub fn element(mut self) -> Self {
+        self.element = true;
+        self
+    }
+
+    // Display object and class references as `common::ObjPtr<T>` and
+    // `common::ClassPtr<T>` instead of `*mut T`.
+    pub fn wrap_pointers(mut self, wrap_pointers: bool) -> Self {
+        self.wrap_pointers = wrap_pointers;
+        self
+    }
+
+    // A property nested in this one, e.g. the element type of an array.
+    fn inner(&self, property: *const FProperty) -> Self {
+        Self::new(property, self.package, self.is_struct_blueprint_generated)
+            .wrap_pointers(self.wrap_pointers)
+            .shorten_paths(self.shorten_paths)
+    }
+}
+
+// See `FProperty::is_plain_old_data()`. Includes inherited fields.
+pub unsafe fn is_plain_old_data(structure: *const UStruct) -> bool {
+    let mut current = structure;
+
+    while !current.is_null() {
+        let mut property = (*current).ChildProperties.cast::<FProperty>();
+
+        while !property.is_null() {
+            if !(*property).is_plain_old_data() {
+                return false;
+            }
+
+            property = (*property).base.Next.cast();
         }
+
+        current = (*current).SuperStruct;
     }
+
+    true
 }
 
+// Every property class that `PropertyDisplayable` has a type for. Anything
+// else becomes an opaque `[u8; ElementSize]`.
+const MODELED_PROPERTIES: EClassCastFlags = EClassCastFlags(
+    EClassCastFlags::CASTCLASS_FArrayProperty.0
+        | EClassCastFlags::CASTCLASS_FBoolProperty.0
+        | EClassCastFlags::CASTCLASS_FByteProperty.0
+        | EClassCastFlags::CASTCLASS_FClassProperty.0
+        | EClassCastFlags::CASTCLASS_FDelegateProperty.0
+        | EClassCastFlags::CASTCLASS_FDoubleProperty.0
+        | EClassCastFlags::CASTCLASS_FEnumProperty.0
+        | EClassCastFlags::CASTCLASS_FFieldPathProperty.0
+        | EClassCastFlags::CASTCLASS_FFloatProperty.0
+        | EClassCastFlags::CASTCLASS_FInt16Property.0
+        | EClassCastFlags::CASTCLASS_FInt64Property.0
+        | EClassCastFlags::CASTCLASS_FInt8Property.0
+        | EClassCastFlags::CASTCLASS_FIntProperty.0
+        | EClassCastFlags::CASTCLASS_FInterfaceProperty.0
+        | EClassCastFlags::CASTCLASS_FLazyObjectProperty.0
+        | EClassCastFlags::CASTCLASS_FMapProperty.0
+        | EClassCastFlags::CASTCLASS_FMulticastInlineDelegateProperty.0
+        | EClassCastFlags::CASTCLASS_FMulticastSparseDelegateProperty.0
+        | EClassCastFlags::CASTCLASS_FNameProperty.0
+        | EClassCastFlags::CASTCLASS_FObjectProperty.0
+        | EClassCastFlags::CASTCLASS_FSetProperty.0
+        | EClassCastFlags::CASTCLASS_FSoftClassProperty.0
+        | EClassCastFlags::CASTCLASS_FSoftObjectProperty.0
+        | EClassCastFlags::CASTCLASS_FStrProperty.0
+        | EClassCastFlags::CASTCLASS_FStructProperty.0
+        | EClassCastFlags::CASTCLASS_FTextProperty.0
+        | EClassCastFlags::CASTCLASS_FUInt16Property.0
+        | EClassCastFlags::CASTCLASS_FUInt32Property.0
+        | EClassCastFlags::CASTCLASS_FUInt64Property.0
+        | EClassCastFlags::CASTCLASS_FWeakObjectProperty.0,
+);
+
 impl FProperty {
     pub unsafe fn is(&self, property: EClassCastFlags) -> bool {
         (*self.base.ClassPrivate).CastFlags.any(property)
@@ -115,13 +151,164 @@ impl FProperty {
     unsafe fn id(&self) -> EClassCastFlags {
         (*self.base.ClassPrivate).Id
     }
+
+    // UE marks deprecated properties with `CPF_Deprecated`, and by convention
+    // also renames them to end in `_DEPRECATED`. Cooked builds don't always
+    // keep the flag, so check both.
+    pub unsafe fn is_deprecated(&self) -> bool {
+        self.PropertyFlags.contains(EPropertyFlags::CPF_Deprecated)
+            || self.base.NamePrivate.text().ends_with("_DEPRECATED")
+    }
+
+    // `CPF_EditorOnly`, but never a bitfield, which shares its byte with the
+    // bits around it and can't be compiled out on its own.
+    pub unsafe fn is_editor_only(&self) -> bool {
+        let this = self as *const FProperty;
+
+        self.PropertyFlags.contains(EPropertyFlags::CPF_EditorOnly)
+            && !(self.is(EClassCastFlags::CASTCLASS_FBoolProperty) && (*this.cast::<FBoolProperty>()).is_bitfield())
+    }
+
+    // Whether every bit pattern of this property's size is a valid value of
+    // its generated type, and the type holds no pointers. Plain `bool`s don't
+    // count since only 0 and 1 are valid; bitfields are stored as integers
+    // and do.
+    pub unsafe fn is_plain_old_data(&self) -> bool {
+        let this = self as *const FProperty;
+
+        match self.id() {
+            EClassCastFlags::CASTCLASS_FInt8Property
+            | EClassCastFlags::CASTCLASS_FInt16Property
+            | EClassCastFlags::CASTCLASS_FIntProperty
+            | EClassCastFlags::CASTCLASS_FInt64Property
+            | EClassCastFlags::CASTCLASS_FByteProperty
+            | EClassCastFlags::CASTCLASS_FUInt16Property
+            | EClassCastFlags::CASTCLASS_FUInt32Property
+            | EClassCastFlags::CASTCLASS_FUInt64Property
+            | EClassCastFlags::CASTCLASS_FFloatProperty
+            | EClassCastFlags::CASTCLASS_FDoubleProperty
+            | EClassCastFlags::CASTCLASS_FEnumProperty => true,
+
+            EClassCastFlags::CASTCLASS_FBoolProperty => (*this.cast::<FBoolProperty>()).is_bitfield(),
+
+            EClassCastFlags::CASTCLASS_FStructProperty => {
+                is_plain_old_data((*this.cast::<FStructProperty>()).Structure)
+            }
+
+            _ => false,
+        }
+    }
+
+    // Whether this property's type, or the type of anything it contains, is
+    // one we don't model and emit as opaque bytes.
+    pub unsafe fn is_opaque(&self) -> bool {
+        let this = self as *const FProperty;
+        let id = self.id();
+
+        if !MODELED_PROPERTIES.any(id) {
+            return true;
+        }
+
+        match id {
+            EClassCastFlags::CASTCLASS_FArrayProperty => (*(*this.cast::<FArrayProperty>()).Inner).is_opaque(),
+
+            EClassCastFlags::CASTCLASS_FMapProperty => {
+                let map = this.cast::<FMapProperty>();
+                (*(*map).KeyProp).is_opaque() || (*(*map).ValueProp).is_opaque()
+            }
+
+            EClassCastFlags::CASTCLASS_FSetProperty => (*(*this.cast::<FSetProperty>()).ElementProp).is_opaque(),
+
+            _ => false,
+        }
+    }
+
+    // Whether this property's parameter type implements `Debug`. Pointers do,
+    // as do numbers and `common::FName`.
+    pub unsafe fn is_debug(&self) -> bool {
+        let this = self as *const FProperty;
+
+        match self.id() {
+            EClassCastFlags::CASTCLASS_FInt8Property
+            | EClassCastFlags::CASTCLASS_FInt16Property
+            | EClassCastFlags::CASTCLASS_FIntProperty
+            | EClassCastFlags::CASTCLASS_FInt64Property
+            | EClassCastFlags::CASTCLASS_FUInt16Property
+            | EClassCastFlags::CASTCLASS_FUInt32Property
+            | EClassCastFlags::CASTCLASS_FUInt64Property
+            | EClassCastFlags::CASTCLASS_FFloatProperty
+            | EClassCastFlags::CASTCLASS_FDoubleProperty
+            | EClassCastFlags::CASTCLASS_FBoolProperty
+            | EClassCastFlags::CASTCLASS_FNameProperty
+            | EClassCastFlags::CASTCLASS_FObjectProperty
+            | EClassCastFlags::CASTCLASS_FClassProperty => true,
+
+            EClassCastFlags::CASTCLASS_FByteProperty => (*this.cast::<FByteProperty>()).Enumeration.is_null(),
+
+            _ => false,
+        }
+    }
+
+    // Calls `f` with every struct, class, and enum that this property's
+    // generated type names, i.e. the same ones `PropertyDisplayable` resolves.
+    pub unsafe fn for_each_referenced_type(&self, f: &mut dyn FnMut(*const UObject)) {
+        let this = self as *const FProperty;
+
+        match self.id() {
+            EClassCastFlags::CASTCLASS_FObjectProperty
+            | EClassCastFlags::CASTCLASS_FWeakObjectProperty
+            | EClassCastFlags::CASTCLASS_FSoftObjectProperty => {
+                f((*this.cast::<FObjectPropertyBase>()).PropertyClass.cast());
+            }
+
+            EClassCastFlags::CASTCLASS_FClassProperty => {
+                f((*this.cast::<FClassProperty>()).MetaClass.cast());
+            }
+
+            EClassCastFlags::CASTCLASS_FSoftClassProperty => {
+                f((*this.cast::<FSoftClassProperty>()).MetaClass.cast());
+            }
+
+            EClassCastFlags::CASTCLASS_FInterfaceProperty => {
+                f((*this.cast::<FInterfaceProperty>()).InterfaceClass.cast());
+            }
+
+            EClassCastFlags::CASTCLASS_FStructProperty => {
+                f((*this.cast::<FStructProperty>()).Structure.cast());
+            }
+
+            EClassCastFlags::CASTCLASS_FEnumProperty => {
+                f((*this.cast::<FEnumProperty>()).Enumeration.cast());
+            }
+
+            EClassCastFlags::CASTCLASS_FByteProperty => {
+                f((*this.cast::<FByteProperty>()).Enumeration.cast());
+            }
+
+            EClassCastFlags::CASTCLASS_FArrayProperty => {
+                (*(*this.cast::<FArrayProperty>()).Inner).for_each_referenced_type(f);
+            }
+
+            EClassCastFlags::CASTCLASS_FMapProperty => {
+                let map = this.cast::<FMapProperty>();
+                (*(*map).KeyProp).for_each_referenced_type(f);
+                (*(*map).ValueProp).for_each_referenced_type(f);
+            }
+
+            EClassCastFlags::CASTCLASS_FSetProperty => {
+                (*(*this.cast::<FSetProperty>()).ElementProp).for_each_referenced_type(f);
+            }
+
+            _ => {}
+        }
+    }
 }
 
 impl Display for PropertyDisplayable {
     fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
         unsafe {
             let array_dim = (*self.property).ArrayDim;
-            let is_array = array_dim > 1;
+            let is_array = array_dim > 1 && !self.element;
 
             if is_array {
                 '['.fmt(f)?;
@@ -132,7 +319,7 @@ impl Display for PropertyDisplayable {
                     let name = (*$property).name();
                     let package = (*$property).package();
 
-                    if package == self.package {
+                    if package == self.package || self.can_shorten($property.cast()) {
                         name.fmt(f)?
                     } else {
                         write!(f, "crate::{}::{}", (*package).short_name(), name)?
@@ -144,7 +331,9 @@ impl Display for PropertyDisplayable {
                     let package = (*$property).package();
                     let is_in_blueprint_module =
                         self.is_struct_blueprint_generated && (*$property).is_blueprint_generated();
-                    let same_package = is_in_blueprint_module || package == self.package;
+                    let same_package = is_in_blueprint_module
+                        || package == self.package
+                        || self.can_shorten($property.cast());
 
                     if same_package {
                         write!(f, $custom_format, name)?
@@ -162,7 +351,12 @@ impl Display for PropertyDisplayable {
             match (*self.property).id() {
                 EClassCastFlags::CASTCLASS_FObjectProperty => {
                     let property = self.property.cast::<FObjectPropertyBase>();
-                    emit_package_qualified_type!((*property).PropertyClass, "*mut {}");
+
+                    if self.wrap_pointers {
+                        emit_package_qualified_type!((*property).PropertyClass, "common::ObjPtr<{}>");
+                    } else {
+                        emit_package_qualified_type!((*property).PropertyClass, "*mut {}");
+                    }
                 }
 
                 EClassCastFlags::CASTCLASS_FStructProperty => {
@@ -180,7 +374,7 @@ impl Display for PropertyDisplayable {
                     write!(
                         f,
                         "common::TArray<{}>",
-                        Self::new(property, self.package, self.is_struct_blueprint_generated)
+                        self.inner(property)
                     )?;
                 }
 
@@ -206,35 +400,42 @@ impl Display for PropertyDisplayable {
                     }
                 }
 
+                // The real type rather than its raw `u64` layout, so that name
+                // fields print as text through `common::FName`'s `Display`.
                 EClassCastFlags::CASTCLASS_FNameProperty => "common::FName".fmt(f)?,
 
                 EClassCastFlags::CASTCLASS_FStrProperty => "common::FString".fmt(f)?,
 
                 EClassCastFlags::CASTCLASS_FClassProperty => {
                     let property = self.property.cast::<FClassProperty>();
-                    emit_package_qualified_type!((*property).MetaClass, "*mut {}");
+
+                    if self.wrap_pointers {
+                        emit_package_qualified_type!((*property).MetaClass, "common::ClassPtr<{}>");
+                    } else {
+                        emit_package_qualified_type!((*property).MetaClass, "*mut {}");
+                    }
                 }
 
                 EClassCastFlags::CASTCLASS_FTextProperty => "common::FText".fmt(f)?,
 
                 EClassCastFlags::CASTCLASS_FMapProperty => {
                     let map = self.property.cast::<FMapProperty>();
+                    let key = self.inner((*map).KeyProp);
+                    let value = self.inner((*map).ValueProp);
 
-                    write!(
-                        f,
-                        "[u8; {}] /* Maps {} to {} */",
-                        (*self.property).ElementSize,
-                        Self::new(
-                            (*map).KeyProp,
-                            self.package,
-                            self.is_struct_blueprint_generated
-                        ),
-                        Self::new(
-                            (*map).ValueProp,
-                            self.package,
-                            self.is_struct_blueprint_generated
-                        )
-                    )?;
+                    // Fall back to opaque bytes if this map doesn't have the
+                    // layout we know, so that later fields keep their offsets.
+                    if (*self.property).ElementSize as usize == common::TMAP_SIZE {
+                        write!(f, "common::TMap<{}, {}>", key, value)?;
+                    } else {
+                        write!(
+                            f,
+                            "[u8; {}] /* Maps {} to {} */",
+                            (*self.property).ElementSize,
+                            key,
+                            value
+                        )?;
+                    }
                 }
 
                 EClassCastFlags::CASTCLASS_FWeakObjectProperty => {
@@ -272,11 +473,7 @@ impl Display for PropertyDisplayable {
                         f,
                         "[u8; {}] /* Set of {} */",
                         (*self.property).ElementSize,
-                        Self::new(
-                            (*set).ElementProp,
-                            self.package,
-                            self.is_struct_blueprint_generated
-                        ),
+                        self.inner((*set).ElementProp),
                     )?;
                 }
 
@@ -314,10 +511,12 @@ impl Display for PropertyDisplayable {
 
                 EClassCastFlags::CASTCLASS_FInt64Property => "i64".fmt(f)?,
 
+                // Correctly sized, so the rest of the struct keeps its layout.
                 id => write!(
                     f,
-                    "[u8; {}] /* WARN: UNKNOWN PROPERTY TYPE Id=={}, Address=={}*/",
+                    "[u8; {}] /* WARN: UNKNOWN PROPERTY TYPE {}, Id=={}, Address=={}*/",
                     (*self.property).ElementSize,
+                    (*(*self.property).base.ClassPrivate).Name,
                     id.0,
                     self.property as usize
                 )?,
@@ -379,6 +578,12 @@ pub struct FArrayProperty {
     pad: [u8; 8],
 }
 
+impl FArrayProperty {
+    pub fn inner(&self) -> *const FProperty {
+        self.Inner
+    }
+}
+
 #[repr(C)]
 pub struct FEnumProperty {
     pub base: FProperty,
@@ -400,6 +605,16 @@ pub struct FMapProperty {
     pad: [u8; 32],
 }
 
+impl FMapProperty {
+    pub fn key(&self) -> *const FProperty {
+        self.KeyProp
+    }
+
+    pub fn value(&self) -> *const FProperty {
+        self.ValueProp
+    }
+}
+
 #[repr(C)]
 pub struct FSetProperty {
     pub base: FProperty,
@@ -430,8 +645,25 @@ pub struct UEnum {
 
 impl_deref! { UEnum as UField }
 
-#[repr(C)]
-pub struct TPair<K, V> {
-    pub Key: K,
-    pub Value: V,
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::mock;
+    use core::mem;
+
+    fn property(name: &str, flags: EPropertyFlags) -> FProperty {
+        let mut property: FProperty = unsafe { mem::zeroed() };
+        property.base.NamePrivate = mock::name(name);
+        property.PropertyFlags = flags;
+        property
+    }
+
+    #[test]
+    fn properties_are_deprecated_by_flag_or_by_name() {
+        unsafe {
+            assert!(property("Ammo", EPropertyFlags::CPF_Deprecated).is_deprecated());
+            assert!(property("Ammo_DEPRECATED", EPropertyFlags::CPF_None).is_deprecated());
+            assert!(!property("Ammo", EPropertyFlags::CPF_None).is_deprecated());
+        }
+    }
 }
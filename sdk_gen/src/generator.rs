@@ -1,20 +1,59 @@
 use crate::buf_writer::BufWriter;
 use crate::game::{
-    self, EPropertyFlags, FBoolProperty, FProperty, PropertyDisplayable, TPair, UEnum,
+    self, FArrayProperty, FBoolProperty, FMapProperty, FProperty, PropertyDisplayable, TPair,
+    UEnum,
 };
 use crate::{sdk_file, sdk_path};
 
 use common::win::file::{self, File};
 use common::{Hex, List, SplitIterator};
 use common::{
-    EClassCastFlags, FName, GUObjectArray, UClass, UFunction, UObject, UPackage, UStruct,
+    EClassCastFlags, FName, GameMemory, LiveMemory, UClass, UFunction, UObject, UPackage, UStruct,
+    MAX_OUTERS,
 };
 
 use core::cell::Cell;
 use core::cmp::Ordering;
+use core::iter;
 use core::fmt::{self, Display, Formatter, Write};
+use core::mem;
+use core::ptr;
 use core::str;
 
+mod conversions;
+
+mod features;
+
+pub(crate) mod imports;
+
+mod intern;
+use intern::Interned;
+
+mod json;
+
+#[cfg(test)]
+pub(crate) mod mock;
+
+mod names;
+
+mod orphans;
+
+pub mod options;
+pub use options::{CargoToml, FunctionFeatures, GeneratorOptions};
+
+// For choosing an edition in `GENERATOR_OPTIONS`.
+#[allow(unused_imports)]
+pub use options::Edition;
+use options::BLUEPRINT_GENERATED_BUFFER_SIZE;
+
+mod parameters;
+
+mod validator;
+use parameters::{
+    DebugFields, DeclareStructFields, InitStructFields, Inputs, Outputs, Parameters, ReturnValues,
+    Kind, TypedefFields,
+};
+
 #[derive(macros::NoPanicErrorDebug)]
 pub enum Error {
     Game(#[from] game::Error),
@@ -25,15 +64,108 @@ pub enum Error {
     BadBitfieldSize(u8),
     LastBitfield,
     MaxPackages,
+    BadLibRsAttribute(&'static str),
+    MaxSkipClasses,
+    MaxPreludeTypes,
     MaxBitfields,
     BitfieldFull,
 
     MaxParameters,
+    MaxSharedParameters,
+    MaxConversions,
+    MaxFeatures,
+    MaxValidatorChecks,
+
+    ObjectFaulted(usize),
+    TooManyWarnings(u32),
+    StructNotFound,
+}
+
+#[derive(Copy, Clone, Default)]
+pub struct Counts {
+    pub structs: u32,
+    pub enums: u32,
+    pub functions: u32,
+    pub warnings: u32,
+    pub skipped: u32,
+
+    // Fields whose type we don't model and emitted as opaque bytes.
+    pub opaque: u32,
+}
+
+impl Counts {
+    fn add(&mut self, other: Counts) {
+        self.structs += other.structs;
+        self.enums += other.enums;
+        self.functions += other.functions;
+        self.warnings += other.warnings;
+        self.skipped += other.skipped;
+        self.opaque += other.opaque;
+    }
+}
+
+#[derive(Copy, Clone)]
+enum FileKind {
+    Struct,
+    Enum,
+}
+
+// The `{package}/mod.rs` and `{package}/{package}_enums.rs` files of a package
+// split by `GeneratorOptions::split_packages`.
+struct SplitFiles {
+    module: File,
+    enums: File,
 }
 
 struct Package {
     ptr: *mut UPackage,
+
+    // Holds everything in this package unless the package is split, in which
+    // case it only holds structs.
     file: File,
+
+    split: Option<SplitFiles>,
+    counts: Counts,
+}
+
+impl Package {
+    fn file(&mut self, kind: FileKind) -> &mut File {
+        match (&mut self.split, kind) {
+            (Some(split), FileKind::Enum) => &mut split.enums,
+            _ => &mut self.file,
+        }
+    }
+
+    fn doc_file(&mut self) -> &mut File {
+        match &mut self.split {
+            Some(split) => &mut split.module,
+            None => &mut self.file,
+        }
+    }
+
+    // The counts are padded to a fixed width so that `Generator::finish()` can
+    // overwrite the placeholder written at registration in place.
+    unsafe fn write_doc(&mut self) -> Result<(), Error> {
+        let name = (*self.ptr.cast::<UObject>()).name();
+        let counts = self.counts;
+
+        writeln!(
+            self.doc_file(),
+            "//! Package `{}`.\n//!\n//! {:7} structs, {:7} enums, {:7} functions.\n",
+            name,
+            counts.structs,
+            counts.enums,
+            counts.functions,
+        )?;
+
+        Ok(())
+    }
+}
+
+// A type listed in `GeneratorOptions::prelude` and the package it was found in.
+struct PreludeMember {
+    name: &'static str,
+    package: *mut UPackage,
 }
 
 impl Drop for Package {
@@ -44,808 +176,2567 @@ impl Drop for Package {
     }
 }
 
+type BlueprintGeneratedBuffer = List<u8, BLUEPRINT_GENERATED_BUFFER_SIZE>;
+type BlueprintGeneratedFile = BufWriter<File, BLUEPRINT_GENERATED_BUFFER_SIZE, &'static mut BlueprintGeneratedBuffer>;
+
+// Too big for the generating thread's stack. Only one `Generator` is alive at a
+// time, so only one borrows it.
+static mut BLUEPRINT_GENERATED_BUFFER: BlueprintGeneratedBuffer = List::new();
+
 pub struct Generator {
+    options: GeneratorOptions,
     lib_rs: File,
     packages: List<Package, 256>,
-    blueprint_generated_package_file: BufWriter<File>,
+    blueprint_generated_package_file: BlueprintGeneratedFile,
+    objects_scanned: u32,
+    totals: Counts,
+    prelude: List<PreludeMember, 256>,
+    conversions: Option<File>,
+    function_index: Option<BufWriter<File>>,
+    class_table: Option<BufWriter<File>>,
+
+    // `GeneratorOptions::emit_layout_json`'s `layout.json`, and whether
+    // nothing has been written to it yet.
+    layout_json: Option<BufWriter<File>>,
+    is_layout_json_empty: bool,
+    validator: Option<BufWriter<File>>,
+
+    // `Cargo.toml`, open at the end of its `[features]` for
+    // `GeneratorOptions::function_features` and `gate_editor_only_fields`.
+    features: Option<BufWriter<File>>,
+
+    // How many objects each entry of `GeneratorOptions::skip_classes` skipped.
+    skip_counts: List<u32, 64>,
 }
 
 impl Generator {
-    pub unsafe fn new() -> Result<Generator, Error> {
-        let mut lib_rs = File::new(sdk_file!("src/lib.rs"))?;
-        lib_rs.write_str(
-            "\
-            #![no_std]\n\
-            #![allow(dead_code, non_camel_case_types, non_snake_case, non_upper_case_globals)]\n\
-            #![allow(clippy::missing_safety_doc, clippy::too_many_arguments, clippy::type_complexity)]\n\
-            pub mod blueprint_generated;\n",
-        )?;
+    pub unsafe fn new(options: GeneratorOptions) -> Result<Generator, Error> {
+        intern::reset();
+        conversions::reset();
+        features::reset();
+        imports::reset();
+        names::reset();
+        orphans::reset();
+        validator::reset();
+
+        let features = match options.cargo_toml {
+            Some(manifest) => {
+                let mut file = write_cargo_toml(manifest, &options)?;
+
+                if options.function_features == FunctionFeatures::Off && !options.gate_editor_only_fields {
+                    None
+                } else {
+                    file.write_str("\n[features]\n")?;
 
-        Ok(Generator {
-            lib_rs,
-            packages: List::new(),
-            blueprint_generated_package_file: BufWriter::new(File::new(sdk_file!(
-                "src/blueprint_generated.rs"
-            ))?),
-        })
-    }
+                    if options.gate_editor_only_fields {
+                        file.write_str("editor = []\n")?;
+                    }
 
-    pub unsafe fn generate_sdk(&mut self) -> Result<(), Error> {
-        for object in (*GUObjectArray).iter().filter(|o| !o.is_null()) {
-            if (*object).fast_is(
-                EClassCastFlags::CASTCLASS_UClass | EClassCastFlags::CASTCLASS_UScriptStruct,
-            ) {
-                self.generate_structure(object.cast())?;
-            } else if (*object).fast_is(EClassCastFlags::CASTCLASS_UEnum) {
-                self.generate_enum(object.cast())?;
+                    Some(BufWriter::new(file))
+                }
             }
-        }
-        Ok(())
-    }
 
-    unsafe fn get_package(&mut self, object: *mut UObject) -> Result<&mut Package, Error> {
-        let package = (*object).package_mut();
-        let is_unseen_package = (*package).PIEInstanceID == -1;
+            None => None,
+        };
 
-        if is_unseen_package {
-            self.register_package(package)?;
-        }
+        let mut lib_rs = File::new(sdk_file!("src/lib.rs"))?;
 
-        let package = (*package).PIEInstanceID as usize;
-        Ok(self.packages.get_unchecked_mut(package))
-    }
+        for &attribute in options.lib_rs_attributes {
+            if !is_inner_attribute(attribute) {
+                return Err(Error::BadLibRsAttribute(attribute));
+            }
 
-    unsafe fn get_package_file(
-        &mut self,
-        object: *mut UObject,
-    ) -> Result<BufWriter<&mut File>, Error> {
-        Ok(BufWriter::new(&mut self.get_package(object)?.file))
-    }
+            writeln!(lib_rs, "{}", attribute)?;
+        }
 
-    unsafe fn register_package(&mut self, package: *mut UPackage) -> Result<(), Error> {
-        let package_name = (*package).short_name();
+        if options.common_crate != "common" {
+            writeln!(lib_rs, "extern crate {} as common;", options.common_crate)?;
+        }
 
-        // Create a Rust module file for this package.
-        let file = {
-            let mut path = List::<u8, 260>::new();
-            write!(
-                &mut path,
-                concat!(sdk_path!(), "/src/{}.rs\0"),
-                package_name
-            )?;
-            File::new(path)?
-        };
+        lib_rs.write_str("pub mod blueprint_generated;\n")?;
 
-        // Declare the module in the SDK lib.rs.
-        writeln!(&mut self.lib_rs, "pub mod {};", package_name)?;
+        if options.packages_subdirectory {
+            file::create_directory(sdk_file!("src/packages"))?;
+        }
 
-        // Register this package's index in our package cache.
-        (*package).PIEInstanceID = self.packages.len() as i32;
+        if options.emit_sdk_info {
+            lib_rs.write_str("mod sdk_info;\n")?;
+        }
 
-        let p = Package { ptr: package, file };
+        let mut skip_counts = List::new();
 
-        // Save the package to our cache.
-        self.packages.push(p).map_err(|_| Error::MaxPackages)?;
+        for _ in options.skip_classes {
+            skip_counts.push(0).map_err(|_| Error::MaxSkipClasses)?;
+        }
 
-        Ok(())
-    }
+        let function_index = if options.emit_function_index {
+            lib_rs.write_str("pub mod functions;\n")?;
+            let mut file = BufWriter::new(File::new(sdk_file!("src/functions.rs"))?);
+            file.write_str("pub static FUNCTIONS: &[(&str, &str)] = &[\n")?;
+            Some(file)
+        } else {
+            None
+        };
 
-    unsafe fn generate_enum(&mut self, enumeration: *mut UEnum) -> Result<(), Error> {
-        let variants = &(*enumeration).Names;
+        let class_table = if options.emit_class_table {
+            lib_rs.write_str("pub mod classes;\n")?;
+            let mut file = BufWriter::new(File::new(sdk_file!("src/classes.rs"))?);
+            file.write_str("use common::StaticClass;\n\npub static CLASSES: &[(&str, unsafe fn() -> *const common::UClass)] = &[\n")?;
+            Some(file)
+        } else {
+            None
+        };
 
-        let (last, rest) = if let Some(v) = variants.split_last() {
-            v
+        let layout_json = if options.emit_layout_json {
+            let mut file = BufWriter::new(File::new(sdk_file!("layout.json"))?);
+            json::begin(&mut file)?;
+            Some(file)
         } else {
-            // Don't generate empty enums.
-            return Ok(());
+            None
         };
 
-        let is_last_variant_autogenerated_max = {
-            let last = last.Key.text();
-            last.ends_with("_MAX") || last.ends_with("_Max")
+        let validator = if options.emit_validator {
+            lib_rs.write_str("pub mod validate;\n")?;
+            let mut file = BufWriter::new(File::new(sdk_file!("src/validate.rs"))?);
+            file.write_str(include_str!("validate.fmt"))?;
+            Some(file)
+        } else {
+            None
         };
 
-        let representation = if is_last_variant_autogenerated_max {
-            get_enum_representation(rest)
+        let conversions = if options.emit_conversions {
+            lib_rs.write_str("mod conversions;\n")?;
+            Some(File::new(sdk_file!("src/conversions.rs"))?)
         } else {
-            get_enum_representation(variants)
+            None
         };
 
-        let mut file = self.get_package_file(enumeration.cast())?;
+        Ok(Generator {
+            options,
+            lib_rs,
+            packages: List::new(),
+            blueprint_generated_package_file: BufWriter::with_buffer(
+                File::new(sdk_file!("src/blueprint_generated.rs"))?,
+                &mut *ptr::addr_of_mut!(BLUEPRINT_GENERATED_BUFFER),
+            ),
+            objects_scanned: 0,
+            totals: Counts::default(),
+            prelude: List::new(),
+            conversions,
+            function_index,
+            class_table,
+            layout_json,
+            is_layout_json_empty: true,
+            validator,
+            features,
+            skip_counts,
+        })
+    }
 
-        writeln!(
-            file,
-            "// {}\n#[repr(transparent)]\n#[derive(Copy, Clone, PartialEq, Eq)]\npub struct {name}({});\n\nimpl {name} {{",
-            *enumeration,
-            representation,
-            name = (*enumeration).name(),
-        )?;
+    pub unsafe fn finish(&mut self) -> Result<(), Error> {
+        if self.options.emit_name_maps {
+            write_name_map(&mut self.blueprint_generated_package_file, ptr::null())?;
+        }
+
+        self.blueprint_generated_package_file.flush()?;
+        common::log!(
+            "Wrote blueprint_generated.rs in {} writes.",
+            self.blueprint_generated_package_file.writes()
+        );
 
-        for variant in rest.iter() {
-            write_enum_variant(&mut file, variant)?;
+        // Before the package docs, which leave the files positioned at their
+        // start.
+        if self.options.emit_package_imports {
+            self.write_imports()?;
         }
 
-        if !is_last_variant_autogenerated_max {
-            write_enum_variant(&mut file, last)?;
+        if self.options.emit_name_maps {
+            for package in self.packages.as_mut_slice() {
+                let ptr = package.ptr;
+                write_name_map(package.file(FileKind::Struct), ptr)?;
+            }
         }
 
-        writeln!(file, "}}\n")?;
+        if let Some(file) = &mut self.function_index {
+            file.write_str("];\n")?;
+            file.flush()?;
+        }
 
-        Ok(())
-    }
+        if let Some(file) = &mut self.layout_json {
+            json::end(&mut *file)?;
+            file.flush()?;
+        }
 
-    unsafe fn generate_structure(&mut self, structure: *mut UStruct) -> Result<(), Error> {
-        if (*structure).fast_is(EClassCastFlags::CASTCLASS_UClass) {
-            let class = structure.cast::<UClass>();
+        if let Some(file) = &mut self.class_table {
+            file.write_str(include_str!("classes.fmt"))?;
+            file.flush()?;
+        }
 
-            if (*class).is_blueprint_generated() {
-                return StructGenerator::new(
-                    structure,
-                    (*class).package(),
-                    &mut self.blueprint_generated_package_file,
-                    true,
-                )
-                .generate();
-            }
+        if let Some(file) = &mut self.validator {
+            validator::write(&mut *file)?;
+            file.write_str("];\n")?;
+            file.flush()?;
         }
 
-        let package = self.get_package(structure.cast())?;
+        self.declare_features()?;
+        self.declare_package_modules()?;
 
-        // TODO(perf): Don't need to create a new `BufWriter` if the previous object is from the same package.
-        // Reuse previous buffer to reduce total `WriteFile` calls.
-        let file = BufWriter::new(&mut package.file);
+        if self.options.emit_package_docs {
+            for package in self.packages.as_mut_slice() {
+                package.doc_file().rewind()?;
+                package.write_doc()?;
+            }
+        }
 
-        StructGenerator::new(structure, package.ptr, file, false).generate()
-    }
-}
+        if !self.options.prelude.is_empty() || !self.options.common_prelude.is_empty() {
+            self.write_prelude()?;
+        }
 
-unsafe fn get_enum_representation(variants: &[TPair<FName, i64>]) -> &'static str {
-    let max_discriminant_value = variants.iter().map(|v| v.Value).max().unwrap_or(0);
+        if !self.options.skip_classes.is_empty() {
+            writeln!(&mut self.lib_rs, "\n// Skipped by `GeneratorOptions::skip_classes`:")?;
 
-    if max_discriminant_value <= u8::MAX.into() {
-        "u8"
-    } else if max_discriminant_value <= u32::MAX.into() {
-        "u32"
-    } else {
-        "u64"
-    }
-}
+            for (name, count) in self.options.skip_classes.iter().zip(self.skip_counts.iter()) {
+                writeln!(&mut self.lib_rs, "//   {}: {} objects", name, count)?;
+            }
+        }
 
-unsafe fn write_enum_variant(
-    mut out: impl Write,
-    variant: &TPair<FName, i64>,
-) -> Result<(), Error> {
-    let mut text = variant.Key.text();
+        if self.options.report_orphans {
+            let orphans = orphans::report();
+            common::log!("Found {} generated types that nothing refers to.", orphans);
+        }
 
-    if let Some(text_stripped) = text
-        .bytes()
-        .rposition(|c| c == b':')
-        .and_then(|i| text.get(i + 1..))
-    {
-        text = text_stripped;
-    }
+        if self.options.emit_sdk_info {
+            self.write_sdk_info()?;
+        }
 
-    if text == "Self" {
-        // `Self` is a Rust keyword.
-        text = "SelfVariant";
+        Ok(())
     }
 
-    if variant.Key.number() > 0 {
-        writeln!(
-            out,
-            "    pub const {}_{}: Self = Self({});",
-            text,
-            variant.Key.number() - 1,
-            variant.Value,
-        )?;
-    } else {
+    unsafe fn write_sdk_info(&self) -> Result<(), Error> {
+        let mut file = File::new(sdk_file!("src/sdk_info.rs"))?;
+        let totals = self.totals;
+
         writeln!(
-            out,
-            "    pub const {}: Self = Self({});",
-            text, variant.Value,
+            file,
+            "// Summary of this SDK, written at the end of the dump.\n//\n// {} packages\n// {} structs\n// {} enums\n// {} functions\n// {} warnings (search the SDK for \"WARN\")\n// {} empty structs and enums skipped\n// {} fields of property types we don't model\n//\n// Options that were on:",
+            self.packages.len(),
+            totals.structs,
+            totals.enums,
+            totals.functions,
+            totals.warnings,
+            totals.skipped,
+            totals.opaque,
         )?;
+
+        for (name, _) in self.options.flags().iter().filter(|(_, on)| *on) {
+            writeln!(file, "//   {}", name)?;
+        }
+
+        Ok(())
     }
 
-    Ok(())
-}
+    unsafe fn write_imports(&mut self) -> Result<(), Error> {
+        let packages = self.packages.as_mut_slice();
+        let mut result = Ok(());
 
-struct StructGenerator<W: Write> {
-    structure: *mut UStruct,
-    package: *const UPackage,
-    out: W,
-    offset: i32,
-    bitfields: List<List<*const FBoolProperty, 64>, 64>,
-    last_bitfield_offset: Option<i32>,
-    is_blueprint_generated: bool,
-    inherited_type: List<u8, 128>,
-    name: CleanedName,
-}
+        imports::for_each(|package, typ| {
+            let index = (*package).PIEInstanceID as usize;
 
-impl<W: Write> StructGenerator<W> {
-    pub unsafe fn new(
-        structure: *mut UStruct,
-        package: *const UPackage,
-        out: W,
-        is_blueprint_generated: bool,
-    ) -> StructGenerator<W> {
-        StructGenerator {
-            structure,
-            package,
-            out,
-            offset: 0,
-            bitfields: List::new(),
-            last_bitfield_offset: None,
-            is_blueprint_generated,
-            inherited_type: List::new(),
-            name: CleanedName::new((*structure).NamePrivate),
-        }
+            if let (Ok(()), Some(p)) = (&result, packages.get_mut(index)) {
+                result = writeln!(
+                    p.file(FileKind::Struct),
+                    "use crate::{}::{};",
+                    (*(*typ).package()).short_name(),
+                    (*typ).name(),
+                );
+            }
+        });
+
+        Ok(result?)
     }
 
-    pub unsafe fn generate(&mut self) -> Result<(), Error> {
-        if (*self.structure).PropertiesSize == 0 {
-            return Ok(());
+    // Packages are registered in whatever order their objects happen to come
+    // in, so sort the module declarations to keep `lib.rs` the same between
+    // runs.
+    unsafe fn declare_package_modules(&mut self) -> Result<(), Error> {
+        let mut names = List::<&str, 256>::new();
+
+        for package in self.packages.iter() {
+            names
+                .push((*package.ptr).short_name())
+                .map_err(|_| Error::MaxPackages)?;
         }
 
-        self.write_header()?;
-        self.add_fields()?;
-        writeln!(self.out, "}}\n")?;
+        for &name in self.options.unchanged_packages {
+            names.push(name).map_err(|_| Error::MaxPackages)?;
+        }
+
+        names.as_mut_slice().sort_unstable();
+
+        // An unchanged package can still have been registered for a
+        // Blueprint-generated type, so skip duplicate names.
+        let mut previous = None;
 
-        if !self.bitfields.is_empty() {
-            self.add_bitfield_getters_and_setters()?;
+        for &name in names.iter() {
+            if previous != Some(name) {
+                write_package_module(&mut self.lib_rs, &self.options, name)?;
+            }
+
+            previous = Some(name);
         }
 
-        self.add_deref_impls()?;
+        Ok(())
+    }
 
-        self.add_functions()?;
+    unsafe fn add_to_validator(&mut self, structure: *const UStruct, is_blueprint_generated: bool) -> Result<(), Error> {
+        if self.validator.is_some() {
+            validator::add(structure, is_blueprint_generated)?;
+        }
 
         Ok(())
     }
 
-    unsafe fn write_header(&mut self) -> Result<(), Error> {
-        let base = (*self.structure).SuperStruct;
+    unsafe fn add_to_class_table(&mut self, structure: *const UStruct, is_blueprint_generated: bool) -> Result<(), Error> {
+        match &mut self.class_table {
+            Some(file) if (*structure).fast_is(EClassCastFlags::CASTCLASS_UClass) => {
+                write_class_table_entry(file, structure, is_blueprint_generated)
+            }
 
-        if base.is_null() {
-            writeln!(
-                self.out,
-                "// {} is {} bytes.\n#[repr(C, align({}))]\npub struct {} {{",
-                *self.structure,
-                Hex((*self.structure).PropertiesSize),
-                (*self.structure).MinAlignment,
-                self.name,
-            )?;
+            _ => Ok(()),
+        }
+    }
+
+    unsafe fn add_struct_to_layout_json(&mut self, structure: *const UStruct, is_blueprint_generated: bool) -> Result<(), Error> {
+        let file = match &mut self.layout_json {
+            Some(file) => file,
+            None => return Ok(()),
+        };
+
+        let package = (*structure).package();
+        let module = package_module_name(package, is_blueprint_generated);
+
+        if is_blueprint_generated {
+            let name = CleanedName::new((&*structure).NamePrivate);
+            json::write_struct(file, self.is_layout_json_empty, structure, name, module, package, true)?;
         } else {
-            self.write_header_inherited(base)?;
+            json::write_struct(file, self.is_layout_json_empty, structure, (*structure).name(), module, package, false)?;
         }
 
+        self.is_layout_json_empty = false;
         Ok(())
     }
 
-    unsafe fn write_header_inherited(&mut self, base: *mut UStruct) -> Result<(), Error> {
-        self.offset = (*base).PropertiesSize;
-
-        writeln!(
-            self.out,
-            "// {} is {} bytes ({} inherited).\n#[repr(C, align({}))]\npub struct {} {{",
-            *self.structure,
-            Hex((*self.structure).PropertiesSize),
-            Hex(self.offset),
-            (*self.structure).MinAlignment,
-            self.name,
-        )?;
-
-        let base_name = (*base).name();
-        let base_package = (*base).package();
-
-        let is_base_blueprint_generated = self.is_blueprint_generated
-            && (*base).fast_is(EClassCastFlags::CASTCLASS_UClass)
-            && (*base.cast::<UClass>()).is_blueprint_generated();
-
-        if is_base_blueprint_generated || base_package == self.package {
-            write!(self.inherited_type, "{}", base_name)?;
-            
-            writeln!(
-                self.out,
-                "    // offset: 0, size: {}\n    base: {},\n",
-                Hex(self.offset), base_name
-            )?;
-        } else {
-            let short_name = (*base_package).short_name();
-
-            write!(self.inherited_type, "crate::{}::{}", short_name, base_name)?;
-
-            writeln!(
-                self.out,
-                "    // offset: 0, size: {}\n    base: crate::{}::{},\n",
-                Hex(self.offset),
-                short_name,
-                base_name
-            )?;
-        }
+    // Same name as `StructGenerator::write_function_cfg()` gates on.
+    unsafe fn add_struct_feature(&mut self, structure: *const UStruct, is_blueprint_generated: bool) -> Result<(), Error> {
+        if self.features.is_some() && self.options.function_features == FunctionFeatures::PerStruct {
+            features::add(structure, is_blueprint_generated)?;
+        }
 
         Ok(())
     }
 
-    unsafe fn add_fields(&mut self) -> Result<(), Error> {
-        let mut property = (*self.structure).ChildProperties.cast::<FProperty>();
+    // Fills in the `[features]` of `Cargo.toml`, sorted like the modules in
+    // `declare_package_modules()`. The packages in `unchanged_packages` keep
+    // the code of an earlier dump, which is gated on features too.
+    unsafe fn declare_features(&mut self) -> Result<(), Error> {
+        let file = match &mut self.features {
+            Some(file) => file,
+            None => return Ok(()),
+        };
 
-        while !property.is_null() {
-            self.process_property(property)?;
-            property = (*property).base.Next.cast();
-        }
+        match self.options.function_features {
+            FunctionFeatures::Off => {}
 
-        self.add_end_of_struct_padding_if_needed()?;
+            FunctionFeatures::PerPackage => {
+                let mut names = List::<&str, 258>::new();
+                names.push("blueprint_generated").map_err(|_| Error::MaxPackages)?;
 
-        Ok(())
-    }
+                for package in self.packages.iter() {
+                    names.push((*package.ptr).short_name()).map_err(|_| Error::MaxPackages)?;
+                }
 
-    unsafe fn process_property(&mut self, property: *const FProperty) -> Result<(), Error> {
-        let size = (*property).ElementSize * (*property).ArrayDim;
+                for &name in self.options.unchanged_packages {
+                    names.push(name).map_err(|_| Error::MaxPackages)?;
+                }
 
-        if size == 0 {
-            return Err(Error::ZeroSizedField);
-        }
+                names.as_mut_slice().sort_unstable();
+                let mut previous = None;
 
-        if (*property).is(EClassCastFlags::CASTCLASS_FBoolProperty) && (*property.cast::<FBoolProperty>()).is_bitfield() {
-            self.process_bool_property(property.cast())?;
-        } else {
-            self.add_padding_if_needed(property)?;
+                for &name in names.iter() {
+                    if previous != Some(name) {
+                        writeln!(file, "fn_{} = []", name)?;
+                    }
 
-            if self.is_blueprint_generated {
-                self.process_blueprint_property(property, size)?;
-            } else {
-                writeln!(
-                    self.out,
-                    "    // offset: {offset}, size: {size}\n    pub {name}: {typ},\n",
-                    offset = Hex(self.offset),
-                    size = Hex(size),
-                    name = (*property).base.NamePrivate,
-                    typ = PropertyDisplayable::new(
-                        property,
-                        self.package,
-                        self.is_blueprint_generated
-                    ),
-                )?;
+                    previous = Some(name);
+                }
             }
 
-            self.offset += size;
-        }
-
-        Ok(())
-    }
-
-    unsafe fn process_bool_property(
-        &mut self,
-        property: *const FBoolProperty,
-    ) -> Result<(), Error> {
-        let offset = (*property).base.Offset;
-
-        if self.last_bitfield_offset.map_or(false, |o| offset == o) {
-            self.bitfields
-                .last_mut()
-                .ok_or(Error::LastBitfield)?
-                .push(property)
-                .map_err(|_| Error::BitfieldFull)?;
-        } else {
-            self.add_padding_if_needed(property.cast())?;
-
-            let size = (*property).FieldSize;
-
-            let representation = if size == 1 {
-                "u8"
-            } else if size == 2 {
-                "u16"
-            } else if size == 4 {
-                "u32"
-            } else if size == 8 {
-                "u64"
-            } else {
-                return Err(Error::BadBitfieldSize(size));
-            };
-
-            writeln!(
-                self.out,
-                "    // offset: {offset}, size: {size}\n    pub bitfield_at_{offset}: {representation},\n",
-                offset = Hex(offset),
-                size = Hex(size),
-                representation = representation,
-            )?;
+            FunctionFeatures::PerStruct => {
+                // Their structs are still in the game, so take the features
+                // from there. That's the same set as long as the game hasn't
+                // been updated since the earlier dump. A struct without
+                // functions gets an unused feature, which is harmless.
+                if !self.options.unchanged_packages.is_empty() {
+                    for object in LiveMemory.objects().filter(|o| !o.is_null()) {
+                        if probe_object(&LiveMemory, object).is_err()
+                            || !(*object).fast_is(EClassCastFlags::CASTCLASS_UClass | EClassCastFlags::CASTCLASS_UScriptStruct)
+                            || ((*object).fast_is(EClassCastFlags::CASTCLASS_UClass) && (*object.cast::<UClass>()).is_blueprint_generated())
+                            || !self.options.unchanged_packages.contains(&(*(*object).package()).short_name())
+                        {
+                            continue;
+                        }
 
-            self.last_bitfield_offset = Some(offset);
+                        let structure = object.cast::<UStruct>();
 
-            self.bitfields
-                .push({
-                    let mut b = List::new();
-                    b.push(property).map_err(|_| Error::BitfieldFull)?;
-                    b
-                })
-                .map_err(|_| Error::MaxBitfields)?;
+                        if (*structure).functions().next().is_some() {
+                            features::add(structure, false)?;
+                        }
+                    }
+                }
 
-            self.offset += i32::from(size);
+                features::write(&mut *file)?;
+            }
         }
 
+        file.flush()?;
         Ok(())
     }
 
-    unsafe fn process_blueprint_property(
-        &mut self,
-        property: *const FProperty,
-        size: i32,
-    ) -> Result<(), Error> {
-        write!(
-            self.out,
-            "    // offset: {offset}, size: {size}\n    pub ",
-            offset = Hex(self.offset),
-            size = Hex(size),
-        )?;
-
-        let name = (*property).base.NamePrivate;
-        let cleaned_name = CleanedName::new(name);
-
-        write!(
-            self.out,
-            "{}: {},",
-            cleaned_name,
-            PropertyDisplayable::new(property, self.package, self.is_blueprint_generated)
-        )?;
+    unsafe fn add_to_function_index(&mut self, structure: *const UStruct) -> Result<(), Error> {
+        let file = match &mut self.function_index {
+            Some(file) => file,
+            None => return Ok(()),
+        };
 
-        let num_invalid_characters_replaced = cleaned_name.num_invalid_characters_replaced.get();
+        for function in (*structure).functions() {
+            let mut full_name = List::<u8, 1024>::new();
+
+            // Leave it out rather than index a truncated name that no
+            // function has.
+            if write!(full_name, "{}", *function).is_err() {
+                common::log!(
+                    "warning: leaving a function of {} out of the function index because its full name is over {} bytes.",
+                    *structure,
+                    full_name.capacity(),
+                );
+                self.totals.warnings += 1;
+                continue;
+            }
 
-        if num_invalid_characters_replaced > 1 {
+            // `Debug` quotes and escapes the names for us.
             writeln!(
-                self.out,
-                "// NOTE: Property's original name is \"{}\". Replaced {} invalid characters.\n",
-                name.text(),
-                num_invalid_characters_replaced
+                file,
+                "    ({:?}, {:?}),",
+                str::from_utf8_unchecked(full_name.as_slice()),
+                (*structure).name(),
             )?;
-        } else {
-            writeln!(self.out, "\n")?;
         }
 
         Ok(())
     }
 
-    unsafe fn add_pad_field(&mut self, from_offset: i32, to_offset: i32) -> Result<(), Error> {
-        writeln!(
-            self.out,
-            "    // offset: {offset}, size: {size}\n    pub pad_at_{offset}: [u8; {size}],\n",
-            offset = Hex(from_offset),
-            size = Hex(to_offset - from_offset),
-        )?;
+    unsafe fn add_conversions(&mut self, structure: *const UStruct) -> Result<(), Error> {
+        let identical = match conversions::find_identical(structure)? {
+            Some(identical) => identical,
+            None => return Ok(()),
+        };
 
-        self.offset = to_offset;
+        if let Some(file) = &mut self.conversions {
+            write_conversion(file, identical, structure)?;
+            write_conversion(file, structure, identical)?;
+        }
 
         Ok(())
     }
 
-    unsafe fn add_padding_if_needed(&mut self, property: *const FProperty) -> Result<(), Error> {
-        let offset = (*property).Offset;
-
-        match self.offset.cmp(&offset) {
-            Ordering::Less => {
-                // We believe the structure is currently at `self.offset`. This
-                // property is some bytes ahead at `offset`. So we need to add
-                // (offset - self.offset) bytes of padding to reach the
-                // property.
-                self.add_pad_field(self.offset, offset)?;
-            }
+    unsafe fn write_prelude(&mut self) -> Result<(), Error> {
+        writeln!(&mut self.lib_rs, "\npub mod prelude {{")?;
 
-            Ordering::Greater => {
-                // The property is some bytes behind our reckoning of the
-                // current offset. Until we figure out a better way to handle
-                // these lagged properties, we should emit a warning so the SDK
-                // user has some idea as to why some fields in some structures
-                // don't line up with what they're seeing in ReClass.
-                writeln!(
-                    self.out,
-                    "    // WARNING: Property \"{}\" thinks its offset is {}. We think its offset is {}.",
-                    (*property).base.NamePrivate, Hex(offset), Hex(self.offset)
-                )?;
-            }
+        for member in self.prelude.iter() {
+            writeln!(
+                &mut self.lib_rs,
+                "    pub use crate::{}::{};",
+                (*member.package).short_name(),
+                member.name,
+            )?;
+        }
 
-            Ordering::Equal => {
-                // Nothing to do. Our reckoning off the current offset matches
-                // the property's offset. No padding or warning required.
+        for &name in self.options.common_prelude {
+            if self.prelude.iter().any(|m| m.name == name) {
+                common::log!("warning: leaving common::{} out of the prelude because a generated {} is already in it.", name, name);
+                self.totals.warnings += 1;
+            } else {
+                writeln!(&mut self.lib_rs, "    pub use common::{};", name)?;
             }
         }
 
-        Ok(())
-    }
-
-    unsafe fn add_end_of_struct_padding_if_needed(&mut self) -> Result<(), Error> {
-        let struct_size = (*self.structure).PropertiesSize;
-
-        match self.offset.cmp(&struct_size) {
-            // See comments in `add_padding_if_needed()` for explanation.
-            Ordering::Less => self.add_pad_field(self.offset, struct_size)?,
-
-            Ordering::Greater => writeln!(
-                self.out,
-                "    // WARNING: This structure thinks its size is {}. We think its size is {}.",
-                Hex(struct_size), Hex(self.offset)
-            )?,
+        writeln!(&mut self.lib_rs, "}}")?;
 
-            Ordering::Equal => {}
+        for &name in self.options.prelude {
+            if !self.prelude.iter().any(|m| m.name == name) {
+                common::log!("warning: prelude type {} wasn't generated.", name);
+            }
         }
 
         Ok(())
     }
 
-    unsafe fn add_bitfield_getters_and_setters(&mut self) -> Result<(), Error> {
-        writeln!(self.out, "impl {} {{", self.name)?;
+    // Records `name` for the prelude if it's listed in the options.
+    unsafe fn add_to_prelude(&mut self, name: &str, package: *mut UPackage) -> Result<(), Error> {
+        let name = match self.options.prelude.iter().find(|&&n| n == name) {
+            Some(&name) => name,
+            None => return Ok(()),
+        };
 
-        for bitfield in self.bitfields.iter() {
-            for &property in bitfield.iter() {
-                let mask = u64::from((*property).ByteMask);
-                let offset = (*property).ByteOffset;
-                let mask = mask << (8 * offset);
-                writeln!(
-                    self.out,
-                    include_str!("bitfield_getter_setter.fmt"),
-                    property_name = (*property).base.base.NamePrivate,
-                    offset = Hex((*property).base.Offset),
-                    mask = mask,
-                )?;
-            }
+        if let Some(existing) = self.prelude.iter().find(|m| m.name == name) {
+            common::log!(
+                "warning: leaving {}::{} out of the prelude because {}::{} is already in it.",
+                (*package).short_name(),
+                name,
+                (*existing.package).short_name(),
+                name,
+            );
+            self.totals.warnings += 1;
+            return Ok(());
         }
 
-        writeln!(self.out, "}}\n")?;
+        self.prelude
+            .push(PreludeMember { name, package })
+            .map_err(|_| Error::MaxPreludeTypes)?;
 
         Ok(())
     }
 
-    unsafe fn add_deref_impls(&mut self) -> Result<(), Error> {
-        if !self.inherited_type.is_empty() {
-            writeln!(
-                self.out,
-                include_str!("deref.fmt"),
-                child = self.name,
-                parent = str::from_utf8_unchecked(self.inherited_type.as_slice()),
-            )?;
+    pub fn print_summary(&self) {
+        let totals = &self.totals;
+
+        common::log!(
+            "Scanned {} objects. Generated {} structs, {} enums, and {} functions in {} packages.",
+            self.objects_scanned,
+            totals.structs,
+            totals.enums,
+            totals.functions,
+            self.packages.len(),
+        );
+
+        common::log!(
+            "{} warnings (search the SDK for \"WARN\"). Skipped {} empty structs and enums.",
+            totals.warnings,
+            totals.skipped,
+        );
+
+        if totals.opaque > 0 {
+            common::log!(
+                "{} fields have property types we don't model and are opaque bytes (search the SDK for \"UNKNOWN PROPERTY TYPE\").",
+                totals.opaque,
+            );
         }
-
-        Ok(())
     }
 
-    unsafe fn add_functions(&mut self) -> Result<(), Error> {
-        let mut property = (*self.structure).Children;
-        let mut has_at_least_one_function = false;
-
-        while !property.is_null() {
-            if (*property).fast_is(EClassCastFlags::CASTCLASS_UFunction) {
-                if !has_at_least_one_function {
-                    has_at_least_one_function = true;
-                    writeln!(self.out, "impl {} {{", self.name)?;
-                }
-
-                self.process_function(property.cast())?;
-            }
+    pub unsafe fn generate_sdk(&mut self) -> Result<(), Error> {
+        self.generate_sdk_from(&LiveMemory)
+    }
 
-            property = (*property).Next;
+    pub unsafe fn generate_sdk_from<M: GameMemory>(&mut self, memory: &M) -> Result<(), Error> {
+        if self.options.emit_package_imports {
+            index_types(memory);
         }
 
-        if has_at_least_one_function {
-            writeln!(self.out, "}}\n")?;
+        for object in memory.objects().filter(|o| !o.is_null()) {
+            self.objects_scanned += 1;
+            self.generate_scanned_object(object)?;
         }
 
+        self.generate_referenced_enums()?;
+
         Ok(())
     }
 
-    unsafe fn process_function(&mut self, function: *const UFunction) -> Result<(), Error> {
-        enum Kind {
-            Input,
-            Output,
+    // Generates only the types of the packages named in `names`, e.g.
+    // `&["FSD", "Engine"]`, plus every type they refer to from other packages,
+    // and the types those refer to in turn, so that the SDK still compiles.
+    // Call `finish()` afterwards as usual. Returns what was generated.
+    //
+    // `lib.rs` only declares the packages that something was generated into,
+    // and `unchanged_packages`. A package that was only pulled in for its
+    // referenced types has its file rewritten with just those types. The files
+    // of a previous dump's other packages are left on disk, undeclared, so
+    // list them in `unchanged_packages` to keep them, in which case whatever
+    // they refer to has to be on disk too.
+    pub unsafe fn generate_packages(&mut self, names: &[&str]) -> Result<Counts, Error> {
+        if self.options.emit_package_imports {
+            index_types(&LiveMemory);
         }
 
-        struct Parameter {
-            property: *const FProperty,
-            kind: Kind,
-        }
+        for object in LiveMemory.objects().filter(|o| !o.is_null()) {
+            self.objects_scanned += 1;
 
-        struct Parameters {
-            parameters: List<Parameter, 32>,
-            package: *const UPackage,
-            is_struct_blueprint_generated: bool,
-            num_outputs: u8,
-        }
+            match is_in_packages(&LiveMemory, object, names) {
+                Ok(true) => self.generate_scanned_object(object)?,
+                Ok(false) => {}
 
-        impl Parameters {
-            fn new(package: *const UPackage, is_struct_blueprint_generated: bool) -> Parameters {
-                Parameters {
-                    parameters: List::new(),
-                    package,
-                    is_struct_blueprint_generated,
-                    num_outputs: 0,
+                Err(Error::ObjectFaulted(address)) => {
+                    common::log!("warning: skipping {} because {} isn't readable.", Hex(object as usize), Hex(address));
+                    self.totals.skipped += 1;
                 }
-            }
-
-            fn add(&mut self, parameter: Parameter) -> Result<(), Error> {
-                self.parameters
-                    .push(parameter)
-                    .map_err(|_| Error::MaxParameters)?;
-                Ok(())
-            }
-
-            fn process(&mut self, property: *const FProperty) -> Result<(), Error> {
-                let flags = unsafe { (*property).PropertyFlags };
-
-                let kind = if flags.contains(EPropertyFlags::CPF_ReturnParm) || (flags.contains(EPropertyFlags::CPF_OutParm) && !flags.contains(EPropertyFlags::CPF_ConstParm)) {
-                    self.num_outputs += 1;
-                    Kind::Output
-                } else if flags.contains(EPropertyFlags::CPF_Parm) {
-                    Kind::Input
-                } else {
-                    return Ok(());
-                };
 
-                self.add(Parameter { property, kind })?;
-
-                Ok(())
+                Err(e) => return Err(e),
             }
         }
 
-        struct Inputs<'a>(&'a Parameters);
+        // Generating a type can make more types referenced, possibly at lower
+        // indices than the one we're at, so go again until a pass finds
+        // nothing new. Types that can't be generated, e.g. because
+        // `skip_classes` matched them, stay ungenerated, so count only the
+        // ones that are.
+        loop {
+            let before = self.totals.structs + self.totals.enums;
+            let mut index = 0;
 
-        impl<'a> Display for Inputs<'a> {
-            fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-                for parameter in self.0.parameters.iter() {
-                    if let Kind::Input = parameter.kind {
-                        let parameter = parameter.property;
-                        let name = CleanedName::new(unsafe { (*parameter).base.NamePrivate });
-                        let typ = PropertyDisplayable::new(
-                            parameter,
-                            self.0.package,
-                            self.0.is_struct_blueprint_generated,
-                        );
-                        write!(f, "{}: {}, ", name, typ)?;
-                    }
+            while let Some((i, object)) = orphans::next_ungenerated(index) {
+                index = i + 1;
+
+                if probe_object(&LiveMemory, object).is_ok() {
+                    self.generate_scanned_object(object)?;
                 }
+            }
 
-                Ok(())
+            if self.totals.structs + self.totals.enums == before {
+                break;
             }
         }
 
-        struct Outputs<'a>(&'a Parameters);
-
-        impl<'a> Display for Outputs<'a> {
-            fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-                match self.0.num_outputs {
-                    0 => return Ok(()),
-                    1 => write!(f, "-> ")?,
-                    _ => write!(f, "-> (")?,
-                }
+        Ok(self.totals)
+    }
 
-                for parameter in self.0.parameters.iter() {
-                    if let Kind::Output = parameter.kind {
-                        let typ = PropertyDisplayable::new(
-                            parameter.property,
-                            self.0.package,
-                            self.0.is_struct_blueprint_generated,
-                        );
-
-                        if self.0.num_outputs == 1 {
-                            write!(f, "{} ", typ)?;
-                            return Ok(());
-                        } else {
-                            write!(f, "{}, ", typ)?;
-                        }
-                    }
-                }
+    unsafe fn generate_scanned_object(&mut self, object: *mut UObject) -> Result<(), Error> {
+        match self.generate_object(object) {
+            Err(Error::ObjectFaulted(address)) => {
+                common::log!("warning: skipping {} because {} isn't readable.", Hex(object as usize), Hex(address));
+                self.totals.skipped += 1;
+            }
 
-                if self.0.num_outputs > 1 {
-                    write!(f, ") ")?;
-                }
+            result => result?,
+        }
 
-                Ok(())
+        if let Some(max) = self.options.max_warnings {
+            if self.totals.warnings > max {
+                return Err(Error::TooManyWarnings(self.totals.warnings));
             }
         }
 
-        struct DeclareStructFields<'a>(&'a Parameters);
+        Ok(())
+    }
+
+    // An enum that a generated field or parameter names has to exist, or the
+    // SDK won't compile. This generates the ones the main pass left out, e.g.
+    // because `skip_classes` matched them. Enums of `unchanged_packages` are
+    // already on disk.
+    unsafe fn generate_referenced_enums(&mut self) -> Result<(), Error> {
+        let mut index = 0;
+
+        while let Some((i, object)) = orphans::next_ungenerated(index) {
+            index = i + 1;
+
+            if !(*object).fast_is(EClassCastFlags::CASTCLASS_UEnum)
+                || self.is_in_unchanged_package(object)
+                || probe_object(&LiveMemory, object).is_err()
+            {
+                continue;
+            }
 
-        impl<'a> Display for DeclareStructFields<'a> {
-            fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-                for parameter in self.0.parameters.iter() {
-                    let property = parameter.property;
-                    let name = CleanedName::new(unsafe { (*property).base.NamePrivate });
-                    let typ = PropertyDisplayable::new(
-                        property,
-                        self.0.package,
-                        self.0.is_struct_blueprint_generated,
-                    );
+            common::log!("Generating {} because generated code refers to it.", *object);
 
-                    if let Kind::Input = parameter.kind {
-                        write!(f, "\n            {}: {}, ", name, typ)?;
-                    } else {
-                        write!(
-                            f,
-                            "\n            {}: core::mem::MaybeUninit<{}>, ",
-                            name, typ
-                        )?;
-                    }
+            match self.generate_enum_object(object) {
+                Err(Error::ObjectFaulted(address)) => {
+                    common::log!("warning: skipping {} because {} isn't readable.", Hex(object as usize), Hex(address));
+                    self.totals.skipped += 1;
                 }
 
-                Ok(())
+                result => result?,
             }
         }
 
-        struct InitStructFields<'a>(&'a Parameters);
+        Ok(())
+    }
 
-        impl<'a> Display for InitStructFields<'a> {
-            fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-                for parameter in self.0.parameters.iter() {
-                    let name = CleanedName::new(unsafe { (*parameter.property).base.NamePrivate });
+    unsafe fn is_skipped(&mut self, object: *mut UObject) -> bool {
+        if self.options.skip_classes.is_empty() {
+            return false;
+        }
 
-                    if let Kind::Input = parameter.kind {
-                        write!(f, "\n            {}, ", name)?;
-                    } else {
-                        write!(
-                            f,
-                            "\n            {}: core::mem::MaybeUninit::uninit(), ",
-                            name
-                        )?;
-                    }
-                }
+        let entry = skip_entry(self.options.skip_classes, object);
 
-                Ok(())
-            }
+        if let Some(count) = entry.and_then(|i| self.skip_counts.as_mut_slice().get_mut(i)) {
+            *count += 1;
+            return true;
         }
 
-        struct ReturnValues<'a>(&'a Parameters);
+        false
+    }
 
-        impl<'a> Display for ReturnValues<'a> {
-            fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
-                match self.0.num_outputs {
-                    0 => return Ok(()),
-                    1 => write!(f, "\n        ")?,
-                    _ => write!(f, "\n        (")?,
-                }
+    unsafe fn is_in_unchanged_package(&self, object: *mut UObject) -> bool {
+        if self.options.unchanged_packages.is_empty() {
+            return false;
+        }
 
-                for parameter in self.0.parameters.iter() {
-                    if let Kind::Output = parameter.kind {
-                        let name =
-                            CleanedName::new(unsafe { (*parameter.property).base.NamePrivate });
+        let is_blueprint_generated = (*object).fast_is(EClassCastFlags::CASTCLASS_UClass)
+            && (*object.cast::<UClass>()).is_blueprint_generated();
 
-                        if self.0.num_outputs == 1 {
-                            write!(f, "parameters.{}.assume_init()", name)?;
-                            return Ok(());
-                        } else {
-                            write!(f, "parameters.{}.assume_init(), ", name)?;
-                        }
-                    }
-                }
+        !is_blueprint_generated
+            && self
+                .options
+                .unchanged_packages
+                .contains(&(*(*object).package()).short_name())
+    }
 
-                if self.0.num_outputs > 1 {
-                    write!(f, ")")?;
-                }
+    unsafe fn generate_object(&mut self, object: *mut UObject) -> Result<(), Error> {
+        probe_object(&LiveMemory, object)?;
 
-                Ok(())
-            }
+        if self.is_skipped(object) || self.is_in_unchanged_package(object) {
+            return Ok(());
         }
 
-        let mut parameters = Parameters::new(self.package, self.is_blueprint_generated);
-        let mut property = (*function).ChildProperties.cast::<FProperty>();
-
-        while !property.is_null() {
-            parameters.process(property)?;
-            property = (*property).base.Next.cast::<FProperty>();
+        if (*object).fast_is(
+            EClassCastFlags::CASTCLASS_UClass | EClassCastFlags::CASTCLASS_UScriptStruct,
+        ) {
+            probe(&LiveMemory, object, mem::size_of::<UStruct>())?;
+            self.generate_structure(object.cast())?;
+        } else if (*object).fast_is(EClassCastFlags::CASTCLASS_UEnum) {
+            self.generate_enum_object(object)?;
         }
 
-        let cleaned_name = CleanedName::new((*function).NamePrivate);
+        Ok(())
+    }
 
+    unsafe fn generate_enum_object(&mut self, object: *mut UObject) -> Result<(), Error> {
+        let enumeration = object.cast::<UEnum>();
+        probe(&LiveMemory, object, mem::size_of::<UEnum>())?;
+        probe(&LiveMemory, (*enumeration).Names.as_ptr(), mem::size_of_val(&*(*enumeration).Names))?;
+        self.generate_enum(enumeration)
+    }
+
+    unsafe fn get_package(&mut self, object: *mut UObject) -> Result<&mut Package, Error> {
+        let package = (*object).package_mut();
+        let is_unseen_package = (*package).PIEInstanceID == -1;
+
+        if is_unseen_package {
+            self.register_package(package)?;
+        }
+
+        let package = (*package).PIEInstanceID as usize;
+        Ok(self.packages.get_unchecked_mut(package))
+    }
+
+    unsafe fn get_package_file(
+        &mut self,
+        object: *mut UObject,
+        kind: FileKind,
+    ) -> Result<BufWriter<&mut File>, Error> {
+        Ok(BufWriter::new(self.get_package(object)?.file(kind)))
+    }
+
+    unsafe fn register_package(&mut self, package: *mut UPackage) -> Result<(), Error> {
+        let package_name = (*package).short_name();
+        let dir = package_dir(&self.options);
+
+        let (file, split) = if self.options.split_packages {
+            let (file, split) = create_split_package_files(dir, package_name)?;
+            (file, Some(split))
+        } else {
+            // Create a Rust module file for this package.
+            let path = package_path(dir, package_name, format_args!(".rs"))?;
+            (File::new(path)?, None)
+        };
+
+        // Register this package's index in our package cache.
+        (*package).PIEInstanceID = self.packages.len() as i32;
+
+        let mut p = Package {
+            ptr: package,
+            file,
+            split,
+            counts: Counts::default(),
+        };
+
+        if self.options.emit_package_docs {
+            p.write_doc()?;
+        }
+
+        if let Some(split) = &mut p.split {
+            write!(
+                split.module,
+                "mod {name}_structs;\npub use {name}_structs::*;\n\nmod {name}_enums;\npub use {name}_enums::*;\n",
+                name = package_name,
+            )?;
+        }
+
+        // Save the package to our cache.
+        self.packages.push(p).map_err(|_| Error::MaxPackages)?;
+
+        Ok(())
+    }
+
+    unsafe fn generate_enum(&mut self, enumeration: *mut UEnum) -> Result<(), Error> {
+        let variants = &(*enumeration).Names;
+
+        let (last, rest) = if let Some(v) = variants.split_last() {
+            v
+        } else {
+            // Don't generate empty enums.
+            self.totals.skipped += 1;
+            return Ok(());
+        };
+
+        self.get_package(enumeration.cast())?.counts.enums += 1;
+        self.totals.enums += 1;
+        orphans::mark_generated(enumeration.cast());
+
+        let is_last_variant_autogenerated_max = {
+            let last = last.Key.text();
+            last.ends_with("_MAX") || last.ends_with("_Max")
+        };
+
+        let known = if is_last_variant_autogenerated_max { rest } else { &variants[..] };
+        let representation = get_enum_representation(known);
+        let options = self.options;
+        let mut file = self.get_package_file(enumeration.cast(), FileKind::Enum)?;
+        write_enum(&mut file, enumeration, known, representation, &options)?;
+        drop(file);
+
+        if let Some(json) = &mut self.layout_json {
+            let package = (*enumeration.cast::<UObject>()).package();
+            json::write_enum(json, self.is_layout_json_empty, enumeration, (*package).short_name(), representation, known)?;
+            self.is_layout_json_empty = false;
+        }
+
+        self.add_to_prelude((*enumeration).name(), (*enumeration.cast::<UObject>()).package_mut())?;
+
+        Ok(())
+    }
+
+    unsafe fn generate_structure(&mut self, structure: *mut UStruct) -> Result<(), Error> {
+        if (*structure).fast_is(EClassCastFlags::CASTCLASS_UClass) {
+            let class = structure.cast::<UClass>();
+
+            if (*class).is_blueprint_generated() {
+                let mut generator = StructGenerator::new(
+                    structure,
+                    (*class).package(),
+                    &mut self.blueprint_generated_package_file,
+                    true,
+                    self.options,
+                );
+                generator.generate()?;
+                let counts = generator.counts;
+                drop(generator);
+
+                self.totals.add(counts);
+
+                if counts.structs > 0 {
+                    self.add_to_function_index(structure)?;
+                    self.add_to_validator(structure, true)?;
+                    self.add_to_class_table(structure, true)?;
+                    self.add_struct_to_layout_json(structure, true)?;
+                }
+
+                if counts.functions > 0 {
+                    self.add_struct_feature(structure, true)?;
+                }
+
+                return Ok(());
+            }
+        }
+
+        let options = self.options;
+        let package = self.get_package(structure.cast())?;
+
+        // TODO(perf): Don't need to create a new `BufWriter` if the previous object is from the same package.
+        // Reuse previous buffer to reduce total `WriteFile` calls.
+        let package_ptr = package.ptr;
+        let file = BufWriter::new(package.file(FileKind::Struct));
+
+        let mut generator = StructGenerator::new(structure, package_ptr, file, false, options);
+        generator.generate()?;
+        let counts = generator.counts;
+        drop(generator);
+
+        package.counts.add(counts);
+        self.totals.add(counts);
+
+        if counts.structs > 0 {
+            // The struct's name as generated, see `StructGenerator::name`.
+            let mut name = List::<u8, 256>::new();
+            write!(name, "{}", CleanedName::new((&*structure).NamePrivate))?;
+
+            self.add_to_prelude(str::from_utf8_unchecked(name.as_slice()), package_ptr)?;
+            self.add_to_function_index(structure)?;
+            self.add_to_validator(structure, false)?;
+            self.add_to_class_table(structure, false)?;
+            self.add_struct_to_layout_json(structure, false)?;
+
+            if self.conversions.is_some() && !(*structure).fast_is(EClassCastFlags::CASTCLASS_UClass) {
+                self.add_conversions(structure)?;
+            }
+        }
+
+        if counts.functions > 0 {
+            self.add_struct_feature(structure, false)?;
+        }
+
+        Ok(())
+    }
+}
+
+// The entry of `GeneratorOptions::skip_classes` that names `object` or its
+// class, if any.
+unsafe fn skip_entry(skip_classes: &[&str], object: *mut UObject) -> Option<usize> {
+    let name = (*object).name();
+    let class_name = (*(*object).class().cast::<UObject>()).name();
+
+    skip_classes.iter().position(|&skipped| skipped == name || skipped == class_name)
+}
+
+// Whether `object` is in one of the packages named in `names`. Finding its
+// package walks its outers, so it's probed first.
+unsafe fn is_in_packages<M: GameMemory>(memory: &M, object: *const UObject, names: &[&str]) -> Result<bool, Error> {
+    probe_object(memory, object)?;
+    Ok(names.contains(&(*(*object).package()).short_name()))
+}
+
+// Records every type that goes into a package module for `imports`. Objects
+// that aren't readable are left for `generate_sdk_from()` to report.
+unsafe fn index_types<M: GameMemory>(memory: &M) {
+    for object in memory.objects().filter(|o| !o.is_null()) {
+        if probe_object(memory, object).is_err() {
+            continue;
+        }
+
+        let is_type = (*object).fast_is(
+            EClassCastFlags::CASTCLASS_UClass
+                | EClassCastFlags::CASTCLASS_UScriptStruct
+                | EClassCastFlags::CASTCLASS_UEnum,
+        );
+
+        let is_blueprint_generated = (*object).fast_is(EClassCastFlags::CASTCLASS_UClass)
+            && (*object.cast::<UClass>()).is_blueprint_generated();
+
+        if is_type && !is_blueprint_generated {
+            imports::add_type(object);
+        }
+    }
+}
+
+// We'd rather catch access violations with SEH, but Rust has no `__try`, and a
+// vectored exception handler would have to resume somewhere sensible, which
+// needs unwinding that a `panic = "abort"` build doesn't have. So instead we
+// check that the memory we're about to read is mapped before reading it.
+//
+// This covers the object, its class, and its outers, plus the struct or enum
+// itself. It doesn't cover pointers further down (e.g. a bad property in a
+// struct's property list), and it can't stop another thread from freeing an
+// object while we generate it.
+unsafe fn probe<M: GameMemory, T>(memory: &M, address: *const T, len: usize) -> Result<(), Error> {
+    if memory.is_readable(address.cast(), len) {
+        Ok(())
+    } else {
+        Err(Error::ObjectFaulted(address as usize))
+    }
+}
+
+unsafe fn probe_object<M: GameMemory>(memory: &M, object: *const UObject) -> Result<(), Error> {
+    probe(memory, object, mem::size_of::<UObject>())?;
+    probe(memory, (*object).class(), mem::size_of::<UClass>())?;
+
+    let mut outer = (*object).outer();
+
+    // A corrupted outer that loops back on itself would otherwise never end.
+    for _ in 0..MAX_OUTERS {
+        if outer.is_null() {
+            break;
+        }
+
+        probe(memory, outer, mem::size_of::<UObject>())?;
+        outer = (*outer).outer();
+    }
+
+    if outer.is_null() {
+        Ok(())
+    } else {
+        Err(Error::ObjectFaulted(outer as usize))
+    }
+}
+
+// Creates `src/{dir}{package}/` and returns its structs file along with its
+// `mod.rs` and enums file. Structs glob-import their parent module so that they
+// can keep naming same-package enums without a path.
+unsafe fn create_split_package_files(dir: &str, package_name: &str) -> Result<(File, SplitFiles), Error> {
+    file::create_directory(&package_path(dir, package_name, format_args!(""))?)?;
+
+    let module = File::new(&package_path(dir, package_name, format_args!("/mod.rs"))?)?;
+    let enums = File::new(&package_path(dir, package_name, format_args!("/{}_enums.rs", package_name))?)?;
+
+    let mut structs = File::new(&package_path(dir, package_name, format_args!("/{}_structs.rs", package_name))?)?;
+    structs.write_str("#[allow(unused_imports)]\nuse super::*;\n\n")?;
+
+    Ok((structs, SplitFiles { module, enums }))
+}
+
+// Where package modules go under `src/`, see
+// `GeneratorOptions::packages_subdirectory`.
+fn package_dir(options: &GeneratorOptions) -> &'static str {
+    if options.packages_subdirectory {
+        "packages/"
+    } else {
+        ""
+    }
+}
+
+// `src/{dir}{package_name}{file}`, nul-terminated for `File::new()`.
+fn package_path(dir: &str, package_name: &str, file: fmt::Arguments) -> Result<List<u8, 260>, Error> {
+    let mut path = List::new();
+    write!(&mut path, concat!(sdk_path!(), "/src/{}{}{}\0"), dir, package_name, file)?;
+    Ok(path)
+}
+
+// The `lib.rs` line declaring package `name`, with a `#[path]` to its module
+// file if that isn't where rustc would look.
+fn write_package_module(mut out: impl Write, options: &GeneratorOptions, name: &str) -> Result<(), Error> {
+    if options.packages_subdirectory {
+        let file = if options.split_packages { "/mod" } else { "" };
+        writeln!(out, "#[path = \"{}{}{}.rs\"]", package_dir(options), name, file)?;
+    }
+
+    writeln!(out, "pub mod {};", name)?;
+
+    Ok(())
+}
+
+// `name` goes into the accessors' names as is, since e.g. `type_at` is fine
+// where `field` has to be `r#type`.
+fn write_array_accessors(
+    mut out: impl Write,
+    cfg: &str,
+    name: impl Display,
+    field: impl Display,
+    element: impl Display,
+) -> Result<(), Error> {
+    writeln!(
+        out,
+        "    {cfg}pub fn {name}_at(&self, i: usize) -> Option<&{element}> {{\n        \
+        self.{field}.get(i)\n    \
+        }}\n\n    \
+        {cfg}pub fn {name}_at_mut(&mut self, i: usize) -> Option<&mut {element}> {{\n        \
+        self.{field}.get_mut(i)\n    \
+        }}\n",
+        cfg = cfg,
+        name = name,
+        field = field,
+        element = element,
+    )?;
+
+    Ok(())
+}
+
+// Only checks the shape, i.e. a single line of `#![...]`. Whether the contents
+// are a valid attribute is up to rustc.
+fn is_inner_attribute(attribute: &str) -> bool {
+    attribute.starts_with("#![") && attribute.ends_with(']') && !attribute.contains('\n')
+}
+
+// `from` and `to` have identical layouts, so the conversion is a plain
+// reinterpretation of the bytes.
+unsafe fn write_conversion(
+    out: &mut File,
+    from: *const UStruct,
+    to: *const UStruct,
+) -> Result<(), Error> {
+    write!(
+        out,
+        "impl From<crate::{from_package}::{from}> for crate::{to_package}::{to} {{\n    \
+        fn from(value: crate::{from_package}::{from}) -> Self {{\n        \
+        unsafe {{ core::mem::transmute(value) }}\n    \
+        }}\n\
+        }}\n\n",
+        from_package = (*(*from).package()).short_name(),
+        from = CleanedName::new((&*from).NamePrivate),
+        to_package = (*(*to).package()).short_name(),
+        to = CleanedName::new((&*to).NamePrivate),
+    )?;
+
+    Ok(())
+}
+
+unsafe fn write_cargo_toml(manifest: CargoToml, options: &GeneratorOptions) -> Result<File, Error> {
+    let mut file = File::new(sdk_file!("Cargo.toml"))?;
+
+    write!(
+        file,
+        "[package]\n\
+        name = \"{}\"\n\
+        version = \"0.1.0\"\n\
+        edition = \"{}\"\n\
+        \n\
+        [dependencies]\n\
+        {} = {{ path = \"{}\" }}\n\
+        {} = {{ path = \"{}\" }}\n",
+        manifest.crate_name,
+        options.edition.name(),
+        options.common_crate,
+        manifest.common_path,
+        options.macros_crate,
+        manifest.macros_path,
+    )?;
+
+    Ok(file)
+}
+
+// Unsigned unless a variant is negative, e.g. an `INDEX_NONE` of -1, in which
+// case the smallest signed type that holds every variant.
+unsafe fn get_enum_representation(variants: &[TPair<FName, i64>]) -> &'static str {
+    let max_discriminant_value = variants.iter().map(|v| v.Value).max().unwrap_or(0);
+    let min_discriminant_value = variants.iter().map(|v| v.Value).min().unwrap_or(0);
+
+    if min_discriminant_value < 0 {
+        let fits = |min: i64, max: i64| min_discriminant_value >= min && max_discriminant_value <= max;
+
+        if fits(i8::MIN.into(), i8::MAX.into()) {
+            "i8"
+        } else if fits(i16::MIN.into(), i16::MAX.into()) {
+            "i16"
+        } else if fits(i32::MIN.into(), i32::MAX.into()) {
+            "i32"
+        } else {
+            "i64"
+        }
+    } else if max_discriminant_value <= u8::MAX.into() {
+        "u8"
+    } else if max_discriminant_value <= u32::MAX.into() {
+        "u32"
+    } else {
+        "u64"
+    }
+}
+
+// `known` are the variants without the autogenerated `_MAX`, if any.
+unsafe fn write_enum(
+    mut out: impl Write,
+    enumeration: *const UEnum,
+    known: &[TPair<FName, i64>],
+    representation: &str,
+    options: &GeneratorOptions,
+) -> Result<(), Error> {
+    let is_trimmed = options.trim_single_variant_enums && known.len() == 1;
+
+    let emit_deref = options.emit_enum_deref && !is_trimmed;
+    let emit_try_from = options.emit_enum_try_from && !is_trimmed;
+    let emit_reflected = (options.emit_reflected || options.emit_validator) && !is_trimmed;
+
+    writeln!(
+        out,
+        "// {}\n#[repr(transparent)]\n#[derive(Copy, Clone, PartialEq, Eq)]\npub struct {name}({});\n\n#[allow(non_upper_case_globals)]\nimpl {name} {{",
+        *enumeration,
+        representation,
+        name = (*enumeration).name(),
+    )?;
+
+    for variant in known {
+        write_enum_variant(&mut out, variant)?;
+    }
+
+    // One for each constant above.
+    writeln!(out, "\n    pub const VARIANT_COUNT: usize = {};", known.len())?;
+
+    writeln!(
+        out,
+        "\n    pub const fn value(self) -> {} {{\n        self.0\n    }}",
+        representation,
+    )?;
+
+    if emit_try_from {
+        write_enum_try_from(&mut out, known, representation)?;
+    }
+
+    writeln!(out, "}}\n")?;
+
+    if emit_deref {
+        writeln!(
+            out,
+            "impl core::ops::Deref for {} {{\n    type Target = {repr};\n\n    fn deref(&self) -> &{repr} {{\n        &self.0\n    }}\n}}\n",
+            (*enumeration).name(),
+            repr = representation,
+        )?;
+    }
+
+    if emit_reflected {
+        writeln!(
+            out,
+            include_str!("reflected.fmt"),
+            typ = (*enumeration).name(),
+            name = (*enumeration).name(),
+            size = format_args!("core::mem::size_of::<{}>()", representation),
+            align = format_args!("core::mem::align_of::<{}>()", representation),
+        )?;
+    }
+
+    Ok(())
+}
+
+fn write_enum_try_from(
+    mut out: impl Write,
+    variants: &[TPair<FName, i64>],
+    representation: &str,
+) -> Result<(), Error> {
+    write!(
+        out,
+        "\n    pub const fn try_from_{repr}(v: {repr}) -> Option<Self> {{\n        ",
+        repr = representation,
+    )?;
+
+    // A `match` with only `_ => None` would warn that `Some(Self(v))` is never
+    // constructed.
+    if variants.is_empty() {
+        writeln!(out, "let _ = v;\n        None\n    }}")?;
+        return Ok(());
+    }
+
+    write!(out, "match v {{\n            ")?;
+
+    let mut first = true;
+
+    for (i, variant) in variants.iter().enumerate() {
+        // Several constants can share a value, and a repeated pattern would
+        // warn as unreachable.
+        if variants[..i].iter().any(|v| v.Value == variant.Value) {
+            continue;
+        }
+
+        if !first {
+            write!(out, " | ")?;
+        }
+
+        write!(out, "{}", variant.Value)?;
+        first = false;
+    }
+
+    writeln!(out, " => Some(Self(v)),\n            _ => None,\n        }}\n    }}")?;
+    Ok(())
+}
+
+unsafe fn write_enum_variant(
+    mut out: impl Write,
+    variant: &TPair<FName, i64>,
+) -> Result<(), Error> {
+    let mut text = variant.Key.text();
+
+    if let Some(text_stripped) = text
+        .bytes()
+        .rposition(|c| c == b':')
+        .and_then(|i| text.get(i + 1..))
+    {
+        text = text_stripped;
+    }
+
+    if text == "Self" {
+        // `Self` is a Rust keyword.
+        text = "SelfVariant";
+    }
+
+    if variant.Key.number() > 0 {
+        writeln!(
+            out,
+            "    pub const {}_{}: Self = Self({});",
+            text,
+            variant.Key.number() - 1,
+            variant.Value,
+        )?;
+    } else {
+        writeln!(
+            out,
+            "    pub const {}: Self = Self({});",
+            text, variant.Value,
+        )?;
+    }
+
+    Ok(())
+}
+
+struct StructGenerator<W: Write> {
+    structure: *mut UStruct,
+    package: *const UPackage,
+    out: W,
+    offset: i32,
+
+    bitfields: List<List<*const FBoolProperty, 64>, 64>,
+    last_bitfield_offset: Option<i32>,
+    is_blueprint_generated: bool,
+    inherited_type: List<u8, 128>,
+    name: CleanedName,
+    options: GeneratorOptions,
+    counts: Counts,
+}
+
+impl<W: Write> StructGenerator<W> {
+    pub unsafe fn new(
+        structure: *mut UStruct,
+        package: *const UPackage,
+        out: W,
+        is_blueprint_generated: bool,
+        options: GeneratorOptions,
+    ) -> StructGenerator<W> {
+        StructGenerator {
+            structure,
+            package,
+            out,
+            offset: 0,
+            bitfields: List::new(),
+            last_bitfield_offset: None,
+            is_blueprint_generated,
+            inherited_type: List::new(),
+            name: CleanedName::new((&*structure).NamePrivate),
+            options,
+            counts: Counts::default(),
+        }
+    }
+
+    pub unsafe fn generate(&mut self) -> Result<(), Error> {
+        if (*self.structure).PropertiesSize == 0 {
+            self.counts.skipped += 1;
+            return Ok(());
+        }
+
+        self.counts.structs += 1;
+        self.mark_references();
+
+        self.write_header()?;
+        self.add_fields()?;
+        writeln!(self.out, "}}\n")?;
+
+        if self.options.emit_container_asserts {
+            self.add_container_asserts()?;
+        }
+
+        if self.options.emit_offset_asserts {
+            self.add_offset_asserts()?;
+        }
+
+        self.add_bitfield_getters_and_setters()?;
+
+        if self.options.emit_array_accessors {
+            self.add_array_accessors()?;
+        }
+
+        if self.options.emit_pointer_iterators {
+            self.add_pointer_iterators()?;
+        }
+
+        if self.options.emit_property_flags {
+            self.add_property_flags()?;
+        }
+
+        if self.options.emit_layout_strings {
+            self.add_layout_string()?;
+        }
+
+        self.add_deref_impls()?;
+
+        if self.is_root_class() {
+            self.add_object_accessors()?;
+        }
+
+        if self.options.emit_reflected || self.options.emit_validator {
+            writeln!(
+                self.out,
+                include_str!("reflected.fmt"),
+                typ = self.name,
+                name = (*self.structure).name(),
+                size = Hex((*self.structure).PropertiesSize),
+                align = (*self.structure).MinAlignment,
+            )?;
+        }
+
+        if (*self.structure).fast_is(EClassCastFlags::CASTCLASS_UClass) {
+            self.add_static_class()?;
+        } else if self.options.emit_pod_bytes && game::is_plain_old_data(self.structure) {
+            writeln!(self.out, include_str!("pod_bytes.fmt"), name = self.name)?;
+        }
+
+        if self.options.emit_spawn_helpers && self.is_actor() {
+            self.add_spawn_helper()?;
+        }
+
+        self.add_functions()?;
+
+        if self.options.emit_function_typedefs {
+            self.add_function_typedefs()?;
+        }
+
+        Ok(())
+    }
+
+    unsafe fn mark_references(&self) {
+        orphans::mark_generated(self.structure.cast());
+        orphans::mark_referenced((*self.structure).SuperStruct.cast());
+
+        let mut property = (*self.structure).ChildProperties.cast::<FProperty>();
+
+        while !property.is_null() {
+            orphans::mark_property(property);
+            property = (*property).base.Next.cast();
+        }
+
+        for function in (*self.structure).functions() {
+            let mut parameter = (&*function).ChildProperties.cast::<FProperty>();
+
+            while !parameter.is_null() {
+                orphans::mark_property(parameter);
+                parameter = (*parameter).base.Next.cast();
+            }
+        }
+    }
+
+    unsafe fn write_header(&mut self) -> Result<(), Error> {
+        let base = (*self.structure).SuperStruct;
+
+        if self.options.emit_outer_chains {
+            writeln!(self.out, "// Outers: {}", (*self.structure).outer_chain())?;
+        }
+
+        if base.is_null() {
+            writeln!(self.out, "// {} is {} bytes.", *self.structure, Hex((*self.structure).PropertiesSize))?;
+
+            if self.options.compact_structs {
+                writeln!(
+                    self.out,
+                    "common::define_struct! {{\n    {}: align({});",
+                    self.name,
+                    (*self.structure).MinAlignment,
+                )?;
+            } else {
+                writeln!(
+                    self.out,
+                    "#[repr(C, align({}))]\npub struct {} {{",
+                    (*self.structure).MinAlignment,
+                    self.name,
+                )?;
+            }
+        } else {
+            self.write_header_inherited(base)?;
+        }
+
+        Ok(())
+    }
+
+    unsafe fn write_header_inherited(&mut self, base: *mut UStruct) -> Result<(), Error> {
+        // The `base` field takes up the base's whole Rust size, tail padding
+        // included, so a field the engine packed into that padding comes out
+        // as an offset warning rather than silently misplaced.
+        self.offset = rust_size(base);
+
+        writeln!(
+            self.out,
+            "// {} is {} bytes ({} inherited).",
+            *self.structure,
+            Hex((*self.structure).PropertiesSize),
+            Hex(self.offset),
+        )?;
+
+        let base_name = (*base).name();
+        let base_package = (*base).package();
+
+        let is_base_blueprint_generated = self.is_blueprint_generated
+            && (*base).fast_is(EClassCastFlags::CASTCLASS_UClass)
+            && (*base.cast::<UClass>()).is_blueprint_generated();
+
+        if is_base_blueprint_generated || base_package == self.package {
+            write!(self.inherited_type, "{}", base_name)?;
+        } else {
+            let short_name = (*base_package).short_name();
+            write!(self.inherited_type, "crate::{}::{}", short_name, base_name)?;
+        }
+
+        let base_type = str::from_utf8_unchecked(self.inherited_type.as_slice());
+
+        if self.options.compact_structs {
+            writeln!(
+                self.out,
+                "common::define_struct! {{\n    {}: align({}), base({});",
+                self.name,
+                (*self.structure).MinAlignment,
+                base_type,
+            )?;
+        } else {
+            writeln!(
+                self.out,
+                "#[repr(C, align({}))]\npub struct {} {{\n    // offset: 0, size: {}\n    base: {},\n",
+                (*self.structure).MinAlignment,
+                self.name,
+                Hex(self.offset),
+                base_type,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    unsafe fn add_fields(&mut self) -> Result<(), Error> {
+        let mut property = (*self.structure).ChildProperties.cast::<FProperty>();
+        let mut previous: *const FProperty = ptr::null();
+
+        while !property.is_null() {
+            if !previous.is_null() && (*property).Offset < (*previous).Offset {
+                self.warn_out_of_order(previous, property)?;
+            }
+
+            self.process_property(property)?;
+            previous = property;
+            property = (*property).base.Next.cast();
+        }
+
+        self.add_end_of_struct_padding_if_needed()?;
+
+        Ok(())
+    }
+
+    // The engine links a structure's properties in offset order, and bitfields
+    // share an offset, so offsets never go down. If they do, we've misread the
+    // property list, e.g. `FProperty`'s layout changed, and more than this
+    // structure is likely wrong. Unlike the overlap warning in
+    // `add_padding_if_needed()`, this is also logged.
+    unsafe fn warn_out_of_order(&mut self, previous: *const FProperty, property: *const FProperty) -> Result<(), Error> {
+        self.counts.warnings += 1;
+
+        common::log!(
+            "warning: {} lists \"{}\" at {} after \"{}\" at {}. The property list may be misread.",
+            *self.structure,
+            (*property).base.NamePrivate,
+            Hex((*property).Offset),
+            (*previous).base.NamePrivate,
+            Hex((*previous).Offset),
+        );
+
+        writeln!(
+            self.out,
+            "    // WARNING: Property \"{}\" at {} comes after \"{}\" at {}. Fields are out of order.",
+            (*property).base.NamePrivate,
+            Hex((*property).Offset),
+            (*previous).base.NamePrivate,
+            Hex((*previous).Offset),
+        )?;
+
+        Ok(())
+    }
+
+    unsafe fn process_property(&mut self, property: *const FProperty) -> Result<(), Error> {
+        // The whole field, since `PropertyDisplayable` types a static array
+        // like `float Foo[8]` as `[f32; 8]`.
+        let size = (*property).ElementSize * (*property).ArrayDim;
+
+        if size == 0 {
+            return Err(Error::ZeroSizedField);
+        }
+
+        if (*property).is(EClassCastFlags::CASTCLASS_FBoolProperty) && (*property.cast::<FBoolProperty>()).is_bitfield() {
+            self.process_bool_property(property.cast())?;
+        } else {
+            self.add_padding_if_needed(property)?;
+
+            if (*property).is_opaque() {
+                self.counts.opaque += 1;
+            }
+
+            // Only the field goes. The padding in front of it, and that of
+            // every field after it, stays the same in both layouts.
+            if self.options.gate_editor_only_fields && (*property).is_editor_only() {
+                writeln!(self.out, "    {}", EDITOR_CFG.trim_end())?;
+            }
+
+            if self.options.emit_deprecated_markers && (*property).is_deprecated() {
+                writeln!(
+                    self.out,
+                    "    #[deprecated(note = \"{} is deprecated and no longer maintained by the game.\")]",
+                    (*property).base.NamePrivate.text(),
+                )?;
+            }
+
+            if self.is_blueprint_generated {
+                self.process_blueprint_property(property, size)?;
+            } else {
+                let typ = self.field_type(property);
+                self.write_field(size, Identifier((*property).base.NamePrivate), typ)?;
+            }
+
+            self.offset += size;
+        }
+
+        Ok(())
+    }
+
+    // Everything but the base field and Blueprint-generated properties, see
+    // `process_blueprint_property()`.
+    fn write_field(&mut self, size: i32, name: impl Display, typ: impl Display) -> Result<(), Error> {
+        if self.options.compact_structs {
+            writeln!(self.out, "    {}: {},", name, typ)?;
+        } else {
+            writeln!(
+                self.out,
+                "    // offset: {}, size: {}\n    pub {}: {},\n",
+                Hex(self.offset),
+                Hex(size),
+                name,
+                typ,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // See `GeneratorOptions::emit_name_maps`.
+    // The `#[cfg]` of everything that names this property's field.
+    unsafe fn field_cfg(&self, property: *const FProperty) -> &'static str {
+        if self.options.gate_editor_only_fields && (*property).is_editor_only() {
+            EDITOR_CFG
+        } else {
+            ""
+        }
+    }
+
+    unsafe fn record_name(&self, name: FName) {
+        if self.options.emit_name_maps {
+            let module = if self.is_blueprint_generated { ptr::null() } else { self.package };
+            names::record(module, name);
+        }
+    }
+
+    fn field_type(&self, property: *const FProperty) -> PropertyDisplayable {
+        PropertyDisplayable::new(property, self.package, self.is_blueprint_generated)
+            .wrap_pointers(self.options.wrap_pointers)
+            .shorten_paths(self.options.emit_package_imports)
+    }
+
+    unsafe fn process_bool_property(
+        &mut self,
+        property: *const FBoolProperty,
+    ) -> Result<(), Error> {
+        let offset = (*property).base.Offset;
+
+        if self.last_bitfield_offset == Some(offset) {
+            self.bitfields
+                .last_mut()
+                .ok_or(Error::LastBitfield)?
+                .push(property)
+                .map_err(|_| Error::BitfieldFull)?;
+        } else {
+            self.add_padding_if_needed(property.cast())?;
+
+            let size = (*property).FieldSize;
+
+            if size == 0 {
+                return Err(Error::BadBitfieldSize(size));
+            }
+
+            let mut representation = List::<u8, 16>::new();
+
+            match bitfield_integer(size) {
+                Some(integer) => representation.write_str(integer)?,
+                None => write!(representation, "[u8; {}]", size)?,
+            }
+
+            let representation = str::from_utf8_unchecked(representation.as_slice());
+
+            if !self.options.compact_structs {
+                writeln!(self.out, "    // offset: {}, size: {}", Hex(offset), Hex(size))?;
+            }
+
+            self.write_bit_annotations(property)?;
+
+            if self.options.compact_structs {
+                writeln!(self.out, "    bitfield_at_{}: {},", Hex(offset), representation)?;
+            } else {
+                writeln!(self.out, "    pub bitfield_at_{}: {},\n", Hex(offset), representation)?;
+            }
+
+            self.last_bitfield_offset = Some(offset);
+
+            self.bitfields
+                .push({
+                    let mut b = List::new();
+                    b.push(property).map_err(|_| Error::BitfieldFull)?;
+                    b
+                })
+                .map_err(|_| Error::MaxBitfields)?;
+
+            self.offset += i32::from(size);
+        }
+
+        Ok(())
+    }
+
+    // Names the bit that each bool of the bitfield starting at `first` occupies
+    // in its storage field. The rest of the group hasn't been processed yet,
+    // so look ahead to the following properties that share its offset.
+    unsafe fn write_bit_annotations(&mut self, first: *const FBoolProperty) -> Result<(), Error> {
+        let offset = (*first).base.Offset;
+        let mut property = first;
+
+        while !property.is_null()
+            && (*property).base.is(EClassCastFlags::CASTCLASS_FBoolProperty)
+            && (*property).is_bitfield()
+            && (*property).base.Offset == offset
+        {
+            let bit = 8 * u32::from((*property).ByteOffset) + (*property).ByteMask.trailing_zeros();
+            writeln!(self.out, "    // bit {}: {}", bit, (*property).base.base.NamePrivate)?;
+            property = (*property).base.base.Next.cast();
+        }
+
+        Ok(())
+    }
+
+    unsafe fn process_blueprint_property(
+        &mut self,
+        property: *const FProperty,
+        size: i32,
+    ) -> Result<(), Error> {
+        if self.options.compact_structs {
+            write!(self.out, "    ")?;
+        } else {
+            write!(
+                self.out,
+                "    // offset: {offset}, size: {size}\n    pub ",
+                offset = Hex(self.offset),
+                size = Hex(size),
+            )?;
+        }
+
+        let name = (*property).base.NamePrivate;
+        let cleaned_name = Identifier(CleanedName::new(name));
+        self.record_name(name);
+
+        write!(
+            self.out,
+            "{}: {},",
+            cleaned_name,
+            self.field_type(property)
+        )?;
+
+        let num_invalid_characters_replaced = cleaned_name.0.num_invalid_characters_replaced.get();
+
+        if num_invalid_characters_replaced > 1 {
+            writeln!(
+                self.out,
+                "// NOTE: Property's original name is \"{}\". Replaced {} invalid characters.\n",
+                name.text(),
+                num_invalid_characters_replaced
+            )?;
+        } else if self.options.compact_structs {
+            writeln!(self.out)?;
+        } else {
+            writeln!(self.out, "\n")?;
+        }
+
+        Ok(())
+    }
+
+    // A container's element type is resolved separately from the container
+    // itself, so a wrong element type doesn't shift any offsets. It does make
+    // iteration read garbage, so check it at SDK compile time instead.
+    unsafe fn add_container_asserts(&mut self) -> Result<(), Error> {
+        let mut property = (*self.structure).ChildProperties.cast::<FProperty>();
+
+        while !property.is_null() {
+            if (*property).is(EClassCastFlags::CASTCLASS_FArrayProperty) {
+                let array = property.cast::<FArrayProperty>();
+                self.add_element_size_assert(property, (*array).inner())?;
+            } else if (*property).is(EClassCastFlags::CASTCLASS_FMapProperty) {
+                let map = property.cast::<FMapProperty>();
+                self.add_element_size_assert(property, (*map).key())?;
+                self.add_element_size_assert(property, (*map).value())?;
+            }
+
+            property = (*property).base.Next.cast();
+        }
+
+        Ok(())
+    }
+
+    // See `GeneratorOptions::emit_offset_asserts`.
+    unsafe fn add_offset_asserts(&mut self) -> Result<(), Error> {
+        let offset_of = self.options.edition.offset_of();
+        writeln!(self.out, "#[cfg(test)]")?;
+
+        // The asserts name deprecated fields too.
+        if self.options.emit_deprecated_markers {
+            writeln!(self.out, "#[allow(deprecated)]")?;
+        }
+
+        writeln!(self.out, "const _: () = {{")?;
+
+        let gate = self.options.gate_editor_only_fields;
+        let base = (*self.structure).SuperStruct;
+        let mut shift = if gate && !base.is_null() { rust_size(base) - runtime_size(base) } else { 0 };
+        let mut property = (*self.structure).ChildProperties.cast::<FProperty>();
+
+        while !property.is_null() {
+            let is_bitfield = (*property).is(EClassCastFlags::CASTCLASS_FBoolProperty)
+                && (*property.cast::<FBoolProperty>()).is_bitfield();
+
+            if !is_bitfield {
+                let name = (*property).base.NamePrivate;
+                let is_editor_only = gate && (*property).is_editor_only();
+
+                for (cfg, offset) in layouts((*property).Offset, shift, is_editor_only) {
+                    write!(self.out, "    {}assert!({}({}, ", cfg, offset_of, self.name)?;
+
+                    if self.is_blueprint_generated {
+                        write!(self.out, "{}", Identifier(CleanedName::new(name)))?;
+                    } else {
+                        write!(self.out, "{}", Identifier(name))?;
+                    }
+
+                    writeln!(self.out, ") == {}, \"{}::{} is at the wrong offset\");", Hex(offset), self.name, CleanedName::new(name))?;
+                }
+
+                if is_editor_only {
+                    shift += (*property).ElementSize * (*property).ArrayDim;
+                }
+            }
+
+            property = (*property).base.Next.cast();
+        }
+
+        let size = rust_size(self.structure);
+        let runtime_size = if gate { runtime_size(self.structure) } else { size };
+
+        for (cfg, size) in layouts(size, size - runtime_size, false) {
+            writeln!(
+                self.out,
+                "    {2}assert!(core::mem::size_of::<{0}>() == {1}, \"{0} has the wrong size\");",
+                self.name,
+                Hex(size),
+                cfg,
+            )?;
+        }
+
+        writeln!(self.out, "}};\n")?;
+
+        Ok(())
+    }
+
+    unsafe fn add_element_size_assert(
+        &mut self,
+        container: *const FProperty,
+        element: *const FProperty,
+    ) -> Result<(), Error> {
+        if element.is_null() {
+            return Ok(());
+        }
+
+        writeln!(
+            self.out,
+            "const _: () = assert!(core::mem::size_of::<{typ}>() == {size}, \"{name}::{field} has the wrong element type\");\n",
+            typ = self.field_type(element),
+            size = Hex((*element).ElementSize),
+            name = self.name,
+            field = CleanedName::new((*container).base.NamePrivate),
+        )?;
+
+        Ok(())
+    }
+
+    unsafe fn add_pad_field(&mut self, from_offset: i32, to_offset: i32) -> Result<(), Error> {
+        let size = to_offset - from_offset;
+
+        self.write_field(
+            size,
+            format_args!("pad_at_{}", Hex(from_offset)),
+            format_args!("[u8; {}]", Hex(size)),
+        )?;
+
+        self.offset = to_offset;
+
+        Ok(())
+    }
+
+    unsafe fn add_padding_if_needed(&mut self, property: *const FProperty) -> Result<(), Error> {
+        let offset = (*property).Offset;
+
+        match self.offset.cmp(&offset) {
+            Ordering::Less => {
+                // We believe the structure is currently at `self.offset`. This
+                // property is some bytes ahead at `offset`. So we need to add
+                // (offset - self.offset) bytes of padding to reach the
+                // property.
+                self.add_pad_field(self.offset, offset)?;
+            }
+
+            Ordering::Greater => {
+                self.counts.warnings += 1;
+
+                // The property is some bytes behind our reckoning of the
+                // current offset. Until we figure out a better way to handle
+                // these lagged properties, we should emit a warning so the SDK
+                // user has some idea as to why some fields in some structures
+                // don't line up with what they're seeing in ReClass.
+                writeln!(
+                    self.out,
+                    "    // WARNING: Property \"{}\" thinks its offset is {}. We think its offset is {}.",
+                    (*property).base.NamePrivate, Hex(offset), Hex(self.offset)
+                )?;
+            }
+
+            Ordering::Equal => {
+                // Nothing to do. Our reckoning off the current offset matches
+                // the property's offset. No padding or warning required.
+            }
+        }
+
+        Ok(())
+    }
+
+    unsafe fn add_end_of_struct_padding_if_needed(&mut self) -> Result<(), Error> {
+        let struct_size = (*self.structure).PropertiesSize;
+        let rounded_size = rust_size(self.structure);
+
+        match self.offset.cmp(&struct_size) {
+            // See comments in `add_padding_if_needed()` for explanation.
+            Ordering::Less => self.add_pad_field(self.offset, struct_size)?,
+
+            // Only the base's tail padding, which this structure's own
+            // rounding adds back anyway.
+            Ordering::Greater if self.offset <= rounded_size => {}
+
+            Ordering::Greater => {
+                self.counts.warnings += 1;
+
+                writeln!(
+                    self.out,
+                    "    // WARNING: This structure thinks its size is {}. We think its size is {}.",
+                    Hex(struct_size), Hex(self.offset)
+                )?;
+            }
+
+            Ordering::Equal => {}
+        }
+
+        if rounded_size != struct_size {
+            writeln!(
+                self.out,
+                "    // NOTE: Rust rounds this structure up to {} bytes, a multiple of its alignment. The last {} bytes are tail padding the engine doesn't count.",
+                Hex(rounded_size),
+                rounded_size - struct_size,
+            )?;
+        }
+
+        Ok(())
+    }
+
+    unsafe fn add_bitfield_getters_and_setters(&mut self) -> Result<(), Error> {
+        let mut has_at_least_one_bit = false;
+
+        for bitfield in self.bitfields.iter() {
+            for &property in bitfield.iter() {
+                if !has_at_least_one_bit {
+                    has_at_least_one_bit = true;
+                    write_accessor_impl(&mut self.out, &self.name, self.options.emit_deprecated_markers)?;
+                }
+
+                let name = (*property).base.base.NamePrivate;
+
+                if bitfield_integer((*property).FieldSize).is_some() {
+                    let mask = u64::from((*property).ByteMask) << (8 * (*property).ByteOffset);
+
+                    writeln!(
+                        self.out,
+                        include_str!("bitfield_getter_setter.fmt"),
+                        getter = Identifier(CleanedName::new(name)),
+                        setter = CleanedName::new(name),
+                        offset = Hex((*property).base.Offset),
+                        mask = mask,
+                    )?;
+                } else {
+                    // Byte-array storage, see `bitfield_integer()`.
+                    writeln!(
+                        self.out,
+                        include_str!("bitfield_bytes_getter_setter.fmt"),
+                        getter = Identifier(CleanedName::new(name)),
+                        setter = CleanedName::new(name),
+                        offset = Hex((*property).base.Offset),
+                        byte = (*property).ByteOffset,
+                        mask = (*property).ByteMask,
+                    )?;
+                }
+            }
+        }
+
+        if has_at_least_one_bit {
+            writeln!(self.out, "}}\n")?;
+        }
+
+        Ok(())
+    }
+
+    unsafe fn add_array_accessors(&mut self) -> Result<(), Error> {
+        let mut has_at_least_one_array = false;
+        let mut property = (*self.structure).ChildProperties.cast::<FProperty>();
+
+        while !property.is_null() {
+            if (*property).ArrayDim > 1 {
+                if !has_at_least_one_array {
+                    has_at_least_one_array = true;
+                    write_accessor_impl(&mut self.out, &self.name, self.options.emit_deprecated_markers)?;
+                }
+
+                let element = self.field_type(property).element();
+                let cfg = self.field_cfg(property);
+
+                // Same spelling as the field itself, see `process_property()`.
+                let name = (*property).base.NamePrivate;
+
+                if self.is_blueprint_generated {
+                    write_array_accessors(&mut self.out, cfg, CleanedName::new(name), Identifier(CleanedName::new(name)), element)?;
+                } else {
+                    write_array_accessors(&mut self.out, cfg, name, Identifier(name), element)?;
+                }
+            }
+
+            property = (*property).base.Next.cast();
+        }
+
+        if has_at_least_one_array {
+            writeln!(self.out, "}}\n")?;
+        }
+
+        Ok(())
+    }
+
+    unsafe fn add_pointer_iterators(&mut self) -> Result<(), Error> {
+        let mut has_at_least_one_iterator = false;
+        let mut property = (*self.structure).ChildProperties.cast::<FProperty>();
+
+        while !property.is_null() {
+            let inner = if (*property).is(EClassCastFlags::CASTCLASS_FArrayProperty) {
+                (*property.cast::<FArrayProperty>()).inner()
+            } else {
+                ptr::null()
+            };
+
+            // Class properties are object properties too.
+            if !inner.is_null() && (*inner).is(EClassCastFlags::CASTCLASS_FObjectProperty) {
+                if !has_at_least_one_iterator {
+                    has_at_least_one_iterator = true;
+                    write_accessor_impl(&mut self.out, &self.name, self.options.emit_deprecated_markers)?;
+                }
+
+                let element = self.field_type(inner);
+                let cfg = self.field_cfg(property);
+
+                // Same spelling as the field itself, see `process_property()`.
+                let name = (*property).base.NamePrivate;
+
+                if self.is_blueprint_generated {
+                    write_pointer_iterator(&mut self.out, cfg, CleanedName::new(name), Identifier(CleanedName::new(name)), element)?;
+                } else {
+                    write_pointer_iterator(&mut self.out, cfg, name, Identifier(name), element)?;
+                }
+            }
+
+            property = (*property).base.Next.cast();
+        }
+
+        if has_at_least_one_iterator {
+            writeln!(self.out, "}}\n")?;
+        }
+
+        Ok(())
+    }
+
+    unsafe fn add_property_flags(&mut self) -> Result<(), Error> {
+        let mut property = (*self.structure).ChildProperties.cast::<FProperty>();
+
+        if property.is_null() {
+            return Ok(());
+        }
+
+        writeln!(self.out, "#[allow(non_upper_case_globals)]\nimpl {} {{", self.name)?;
+
+        while !property.is_null() {
+            let name = (*property).base.NamePrivate;
+            let flags = (*property).PropertyFlags.0;
+
+            // Same spelling as the field itself, see `process_property()`.
+            if self.is_blueprint_generated {
+                write_property_flags(&mut self.out, CleanedName::new(name), flags)?;
+            } else {
+                write_property_flags(&mut self.out, name, flags)?;
+            }
+
+            property = (*property).base.Next.cast();
+        }
+
+        writeln!(self.out, "}}\n")?;
+
+        Ok(())
+    }
+
+    unsafe fn add_layout_string(&mut self) -> Result<(), Error> {
+        write!(self.out, "impl {} {{\n    pub const LAYOUT: &'static str = \"", self.name)?;
+
+        let mut property = (*self.structure).ChildProperties.cast::<FProperty>();
+        let mut is_first = true;
+
+        while !property.is_null() {
+            if !is_first {
+                self.out.write_char(';')?;
+            }
+
+            is_first = false;
+
+            let name = (*property).base.NamePrivate;
+            let offset = Hex((*property).Offset);
+            let size = Hex((*property).ElementSize * (*property).ArrayDim);
+
+            // Same spelling as the field itself, see `process_property()`.
+            if self.is_blueprint_generated {
+                write!(self.out, "{}:{}:{}", offset, size, CleanedName::new(name))?;
+            } else {
+                write!(self.out, "{}:{}:{}", offset, size, name)?;
+            }
+
+            property = (*property).base.Next.cast();
+        }
+
+        writeln!(self.out, "\";\n}}\n")?;
+
+        Ok(())
+    }
+
+    unsafe fn add_deref_impls(&mut self) -> Result<(), Error> {
+        // `common::define_struct!` already has them.
+        if !self.inherited_type.is_empty() && !self.options.compact_structs {
+            writeln!(
+                self.out,
+                include_str!("deref.fmt"),
+                child = self.name,
+                parent = str::from_utf8_unchecked(self.inherited_type.as_slice()),
+            )?;
+        }
+
+        Ok(())
+    }
+
+    // `UObject` itself. Every other class derefs down to it, so accessors
+    // emitted here are available on all generated classes.
+    unsafe fn is_root_class(&self) -> bool {
+        (*self.structure).fast_is(EClassCastFlags::CASTCLASS_UClass)
+            && (*self.structure).SuperStruct.is_null()
+    }
+
+    unsafe fn add_object_accessors(&mut self) -> Result<(), Error> {
+        writeln!(self.out, include_str!("object.fmt"), name = self.name)?;
+
+        if self.options.emit_owner_accessors {
+            writeln!(self.out, include_str!("owner.fmt"), name = self.name)?;
+        }
+
+        Ok(())
+    }
+
+    unsafe fn is_actor(&self) -> bool {
+        (*self.structure).fast_is(EClassCastFlags::CASTCLASS_UClass)
+            && (*self.structure.cast::<UClass>())
+                .ClassCastFlags
+                .any(EClassCastFlags::CASTCLASS_AActor)
+    }
+
+    // Lets `common::try_cast()` check for this class.
+    unsafe fn add_static_class(&mut self) -> Result<(), Error> {
+        writeln!(
+            self.out,
+            include_str!("static_class.fmt"),
+            name = self.name,
+            full_name = *self.structure,
+        )?;
+
+        Ok(())
+    }
+
+    unsafe fn add_spawn_helper(&mut self) -> Result<(), Error> {
+        writeln!(self.out, include_str!("spawn.fmt"), name = self.name)?;
+
+        Ok(())
+    }
+
+    unsafe fn add_functions(&mut self) -> Result<(), Error> {
+        let mut has_at_least_one_function = false;
+
+        for function in (*self.structure).functions() {
+            if !has_at_least_one_function {
+                has_at_least_one_function = true;
+                self.write_function_cfg()?;
+                writeln!(self.out, "impl {} {{", self.name)?;
+            }
+
+            self.process_function(function)?;
+            self.counts.functions += 1;
+        }
+
+        if has_at_least_one_function {
+            writeln!(self.out, "}}\n")?;
+        }
+
+        Ok(())
+    }
+
+    // See `GeneratorOptions::function_features`.
+    unsafe fn write_function_cfg(&mut self) -> Result<(), Error> {
+        let module = package_module_name(self.package, self.is_blueprint_generated);
+
+        match self.options.function_features {
+            FunctionFeatures::Off => {}
+            FunctionFeatures::PerPackage => writeln!(self.out, "#[cfg(feature = \"fn_{}\")]", module)?,
+            FunctionFeatures::PerStruct => writeln!(self.out, "#[cfg(feature = \"fn_{}_{}\")]", module, self.name)?,
+        }
+
+        Ok(())
+    }
+
+    unsafe fn process_function(&mut self, function: *const UFunction) -> Result<(), Error> {
+        let parameters = Parameters::new(function, self.package, self.is_blueprint_generated)?;
+        let cleaned_name = Identifier(CleanedName::new((&*function).NamePrivate));
+        self.record_name((&*function).NamePrivate);
+
+        if !self.options.native_call_notes.is_empty() {
+            self.add_native_call_notes(function, &parameters)?;
+        }
+
+        writeln!(
+            self.out,
+            include_str!("function.fmt"),
+            name = cleaned_name,
+            full_name = *function,
+            inputs = Inputs(&parameters),
+            outputs = Outputs(&parameters),
+            declare_struct_fields = DeclareStructFields(&parameters),
+            init_struct_fields = InitStructFields(&parameters),
+            return_values = ReturnValues(&parameters),
+            flags = (*function).FunctionFlags,
+            size_assert = ParamsSizeAssert(self.options.emit_params_asserts.then(|| (&*function).PropertiesSize)),
+        )?;
+
+        Ok(())
+    }
+
+    // See `GeneratorOptions::native_call_notes`.
+    unsafe fn add_native_call_notes(&mut self, function: *const UFunction, parameters: &Parameters) -> Result<(), Error> {
+        let mut full_name = List::<u8, 1024>::new();
+        write!(full_name, "{}", *function)?;
+        let full_name = str::from_utf8_unchecked(full_name.as_slice());
+
+        if !self.options.native_call_notes.contains(&full_name) {
+            return Ok(());
+        }
+
+        writeln!(
+            self.out,
+            "    // Native call: the parameters take {} bytes, laid out as below, like the\n    \
+            // wrapper's own `Parameters`. Call the native implementation with\n    \
+            // `common::call_native!(object, function, &mut parameters)`, where\n    \
+            // `parameters` is a `#[repr(C)]` struct of that layout and `function` is\n    \
+            // `(*common::GUObjectArray).find_function(\"{}\")`.",
+            Hex((&*function).PropertiesSize),
+            full_name,
+        )?;
+
+        for parameter in parameters.parameters.iter() {
+            let property = parameter.property;
+
+            writeln!(
+                self.out,
+                "    //     {}: {} ({} bytes, {})",
+                Hex((*property).Offset),
+                (*property).base.NamePrivate,
+                Hex((*property).ElementSize * (*property).ArrayDim),
+                if let Kind::Input = parameter.kind { "in" } else { "out" },
+            )?;
+        }
+
+        Ok(())
+    }
+
+    unsafe fn add_function_typedefs(&mut self) -> Result<(), Error> {
+        for function in (*self.structure).functions() {
+            self.add_function_typedef(function)?;
+        }
+
+        Ok(())
+    }
+
+    unsafe fn add_function_typedef(&mut self, function: *const UFunction) -> Result<(), Error> {
+        let parameters = Parameters::new(function, self.package, self.is_blueprint_generated)?;
+
+        let interned = if self.options.share_function_typedefs {
+            intern::intern(function, self.package, self.is_blueprint_generated, &parameters)?
+        } else {
+            None
+        };
+
+        if let Some(interned) = interned {
+            let hash = match interned {
+                Interned::New(hash) => {
+                    writeln!(
+                        self.out,
+                        "// Parameter layout shared by every function whose `_Params` aliases it.\n#[repr(C)]\npub struct Params_{} {{{}\n}}\n",
+                        hash,
+                        TypedefFields(&parameters),
+                    )?;
+
+                    if self.options.emit_params_debug {
+                        write_params_debug(&mut self.out, format_args!("Params_{}", hash), &parameters)?;
+                    }
+
+                    hash
+                }
+
+                Interned::Existing(hash) => hash,
+            };
+
+            writeln!(
+                self.out,
+                "// Parameters of {}. See `GeneratorOptions::emit_function_typedefs` for how to read them in a detour.\npub type {}_{}_Params = Params_{};\n",
+                *function,
+                self.name,
+                CleanedName::new((&*function).NamePrivate),
+                hash,
+            )?;
+
+            return Ok(());
+        }
+
+        writeln!(
+            self.out,
+            "// Parameters of {}. See `GeneratorOptions::emit_function_typedefs` for how to read them in a detour.\n#[repr(C)]\npub struct {}_{}_Params {{{}\n}}\n",
+            *function,
+            self.name,
+            CleanedName::new((&*function).NamePrivate),
+            TypedefFields(&parameters),
+        )?;
+
+        if self.options.emit_params_debug {
+            write_params_debug(
+                &mut self.out,
+                format_args!("{}_{}_Params", self.name, CleanedName::new((&*function).NamePrivate)),
+                &parameters,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// Generates the one struct or class `name` of the package with short name
+// `package` into `out`, exactly as a full dump would, without touching any
+// files, e.g. to look at a layout that came out wrong without re-dumping
+// everything. `name` is the engine's name, e.g. "PlayerCharacter" of "FSD" or
+// "BP_PlayerCharacter_C" of "BP_PlayerCharacter". The output names its base
+// and field types by their paths in the SDK and doesn't compile on its own.
+pub unsafe fn generate_struct_by_name<M: GameMemory>(
+    memory: &M,
+    options: GeneratorOptions,
+    package: &str,
+    name: &str,
+    out: impl Write,
+) -> Result<(), Error> {
+    let structure = memory
+        .objects()
+        .filter(|o| !o.is_null())
+        .find(|&o| {
+            probe_object(memory, o).is_ok()
+                && (*o).fast_is(EClassCastFlags::CASTCLASS_UClass | EClassCastFlags::CASTCLASS_UScriptStruct)
+                && (*o).name() == name
+                && (*(*o).package()).short_name() == package
+        })
+        .ok_or(Error::StructNotFound)?
+        .cast::<UStruct>();
+
+    probe(memory, structure, mem::size_of::<UStruct>())?;
+
+    let is_blueprint_generated = (*structure).fast_is(EClassCastFlags::CASTCLASS_UClass)
+        && (*structure.cast::<UClass>()).is_blueprint_generated();
+
+    StructGenerator::new(structure, (*structure).package(), out, is_blueprint_generated, options).generate()
+}
+
+// The integer that stores a bitfield of `size` bytes. Any other size, which
+// the engine doesn't produce as far as we know, is stored as a byte array
+// instead so that the layout still comes out right, and its bits are
+// addressed by `ByteOffset` and `ByteMask` directly.
+fn bitfield_integer(size: u8) -> Option<&'static str> {
+    match size {
+        1 => Some("u8"),
+        2 => Some("u16"),
+        4 => Some("u32"),
+        8 => Some("u64"),
+        _ => None,
+    }
+}
+
+// `size_of` of a generated struct: Rust rounds sizes up to the alignment, and
+// the engine's `PropertiesSize` needn't be.
+unsafe fn rust_size(structure: *const UStruct) -> i32 {
+    round_up((*structure).PropertiesSize, (*structure).MinAlignment)
+}
+
+// `rust_size()` without the `editor` feature, see
+// `GeneratorOptions::gate_editor_only_fields`.
+unsafe fn runtime_size(structure: *const UStruct) -> i32 {
+    round_up((*structure).PropertiesSize - editor_only_size(structure), (*structure).MinAlignment)
+}
+
+// How many of `structure`'s bytes are gone without the `editor` feature: its
+// editor-only fields, and whatever its base shrinks by.
+unsafe fn editor_only_size(structure: *const UStruct) -> i32 {
+    let base = (*structure).SuperStruct;
+    let mut size = if base.is_null() { 0 } else { rust_size(base) - runtime_size(base) };
+    let mut property = (*structure).ChildProperties.cast::<FProperty>();
+
+    while !property.is_null() {
+        if (*property).is_editor_only() {
+            size += (*property).ElementSize * (*property).ArrayDim;
+        }
+
+        property = (*property).base.Next.cast();
+    }
+
+    size
+}
+
+fn round_up(size: i32, align: i32) -> i32 {
+    let align = align.max(1);
+    (size + align - 1) / align * align
+}
+
+// See `GeneratorOptions::gate_editor_only_fields`.
+const EDITOR_CFG: &str = "#[cfg(feature = \"editor\")] ";
+const RUNTIME_CFG: &str = "#[cfg(not(feature = \"editor\"))] ";
+
+// The `#[cfg]` and value of each assert about something at `offset` in the
+// dumped layout and `shift` bytes earlier without the `editor` feature. An
+// editor-only field only has the one.
+fn layouts(offset: i32, shift: i32, is_editor_only: bool) -> impl Iterator<Item = (&'static str, i32)> {
+    let both = shift != 0 && !is_editor_only;
+    let dumped = if both || is_editor_only { EDITOR_CFG } else { "" };
+    let runtime = both.then_some((RUNTIME_CFG, offset - shift));
+
+    iter::once((dumped, offset)).chain(runtime)
+}
+
+// A `classes::CLASSES` entry, by the same path as `StructGenerator` names the
+// type by.
+unsafe fn write_class_table_entry(mut out: impl Write, class: *const UStruct, is_blueprint_generated: bool) -> Result<(), Error> {
+    if is_blueprint_generated {
+        let name = CleanedName::new((&*class).NamePrivate);
+        writeln!(out, "    (\"{0}\", crate::blueprint_generated::{0}::static_class),", name)?;
+    } else {
         writeln!(
-            self.out,
-            include_str!("function.fmt"),
-            name = cleaned_name,
-            full_name = *function,
-            inputs = Inputs(&parameters),
-            outputs = Outputs(&parameters),
-            declare_struct_fields = DeclareStructFields(&parameters),
-            init_struct_fields = InitStructFields(&parameters),
-            return_values = ReturnValues(&parameters),
-            flags = (*function).FunctionFlags,
+            out,
+            "    (\"{0}\", crate::{1}::{0}::static_class),",
+            (*class).name(),
+            (*(*class).package()).short_name(),
         )?;
+    }
 
-        Ok(())
+    Ok(())
+}
+
+// The module a struct of `package` is generated into.
+unsafe fn package_module_name(package: *const UPackage, is_blueprint_generated: bool) -> &'static str {
+    if is_blueprint_generated {
+        "blueprint_generated"
+    } else {
+        (*package).short_name()
+    }
+}
+
+// Starts an `impl` of accessors, which name deprecated fields too. See
+// `GeneratorOptions::emit_deprecated_markers`.
+fn write_accessor_impl(mut out: impl Write, typ: impl Display, allow_deprecated: bool) -> Result<(), Error> {
+    if allow_deprecated {
+        writeln!(out, "#[allow(deprecated)]")?;
     }
+
+    writeln!(out, "impl {} {{", typ)?;
+    Ok(())
+}
+
+// Like `write_array_accessors()`, `name` is for `iter_{name}` and `field` for
+// the field.
+fn write_pointer_iterator(
+    mut out: impl Write,
+    cfg: &str,
+    name: impl Display,
+    field: impl Display,
+    element: impl Display,
+) -> Result<(), Error> {
+    writeln!(
+        out,
+        "    {cfg}pub fn iter_{name}(&self) -> impl Iterator<Item = {element}> + '_ {{\n        \
+        self.{field}.iter().copied().filter(|p| !p.is_null())\n    \
+        }}\n",
+        cfg = cfg,
+        name = name,
+        field = field,
+        element = element,
+    )?;
+
+    Ok(())
+}
+
+fn write_property_flags(mut out: impl Write, field: impl Display, flags: u64) -> Result<(), Error> {
+    writeln!(
+        out,
+        "    pub const {}_FLAGS: common::EPropertyFlags = common::EPropertyFlags({:#x});",
+        field,
+        flags,
+    )?;
+
+    Ok(())
+}
+
+// See `GeneratorOptions::emit_name_maps`. `package` is null for
+// `blueprint_generated`.
+unsafe fn write_name_map(mut out: impl Write, package: *const UPackage) -> Result<(), Error> {
+    let mut result = writeln!(out, "\npub static NAME_MAP: &[(&str, &str)] = &[");
+
+    names::for_each(package, |name| {
+        let mut original = List::<u8, 1024>::new();
+
+        if result.is_ok() && write!(original, "{}", name).is_ok() {
+            // `Debug` quotes and escapes the original for us.
+            result = writeln!(
+                out,
+                "    (\"{}\", {:?}),",
+                CleanedName::new(name),
+                str::from_utf8_unchecked(original.as_slice()),
+            );
+        }
+    });
+
+    result?;
+    writeln!(out, "];")?;
+
+    Ok(())
+}
+
+// See `GeneratorOptions::emit_params_debug`.
+fn write_params_debug(out: &mut impl Write, name: fmt::Arguments, parameters: &Parameters) -> Result<(), Error> {
+    writeln!(
+        out,
+        "impl core::fmt::Debug for {name} {{\n    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {{\n        f.debug_struct(\"{name}\"){fields}\n            .finish()\n    }}\n}}\n",
+        name = name,
+        fields = DebugFields(parameters),
+    )?;
+
+    Ok(())
 }
 
 struct CleanedName {
@@ -862,6 +2753,82 @@ impl CleanedName {
     }
 }
 
+// Reserved in every edition since 2018. A `CleanedName` can only spell one of
+// these when the name is the keyword itself.
+const KEYWORDS: &[&str] = &[
+    "Self", "abstract", "as", "async", "await", "become", "box", "break", "const", "continue",
+    "crate", "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "static", "struct", "super", "trait", "true", "try", "type", "typeof",
+    "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
+];
+
+// See `GeneratorOptions::emit_params_asserts`. Rust rounds a struct's size up
+// to its alignment and the engine needn't, so compare against the rounded size.
+struct ParamsSizeAssert(Option<i32>);
+
+impl Display for ParamsSizeAssert {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        if let Some(size) = self.0 {
+            write!(
+                f,
+                "\n        const _: () = {{\n            \
+                let align = core::mem::align_of::<Parameters>();\n            \
+                assert!(core::mem::size_of::<Parameters>() == ({} + align - 1) / align * align, \"wrong parameter layout\");\n        \
+                }};\n",
+                Hex(size),
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+// A name used as an identifier on its own, e.g. `fn {}` or `pub {}: T`, either
+// cleaned or, for native names that are already valid identifiers, as is.
+// Keywords become raw identifiers, except for the few that can't be raw and
+// get a trailing underscore instead.
+struct Identifier<T: Named>(T);
+
+trait Named: Display {
+    fn fname(&self) -> FName;
+}
+
+impl Named for CleanedName {
+    fn fname(&self) -> FName {
+        self.name
+    }
+}
+
+impl Named for FName {
+    fn fname(&self) -> FName {
+        *self
+    }
+}
+
+impl<T: Named> Display for Identifier<T> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        let name = self.0.fname();
+
+        if name.number() > 0 {
+            self.0.fmt(f)
+        } else {
+            write_identifier(f, unsafe { name.text() }, &self.0)
+        }
+    }
+}
+
+// Writes `text` as an identifier, or `plain` if it isn't a keyword.
+fn write_identifier(mut out: impl Write, text: &str, plain: impl Display) -> Result<(), fmt::Error> {
+    if !KEYWORDS.contains(&text) {
+        write!(out, "{}", plain)
+    } else if matches!(text, "Self" | "self" | "super" | "crate") {
+        write!(out, "{}_", text)
+    } else {
+        write!(out, "r#{}", text)
+    }
+}
+
 impl Display for CleanedName {
     fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
         let mut num_pieces_added = 0;
@@ -897,3 +2864,782 @@ impl Display for CleanedName {
         Ok(())
     }
 }
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use common::{EPropertyFlags, Snapshot};
+    use core::ptr;
+    use std::boxed::Box;
+    use std::string::String;
+
+    type Output = Box<List<u8, 16384>>;
+
+    // What `StructGenerator` writes for `structure`.
+    fn generate(structure: *mut UStruct, options: GeneratorOptions) -> Output {
+        let mut out = Output::new(List::new());
+
+        unsafe {
+            let _generating = mock::generating();
+            let package = (*structure).package();
+            StructGenerator::new(structure, package, &mut *out, false, options).generate().unwrap();
+        }
+
+        out
+    }
+
+    fn text(out: &Output) -> &str {
+        str::from_utf8(out.as_slice()).unwrap()
+    }
+
+    fn identifier(text: &str) -> List<u8, 64> {
+        let mut out = List::new();
+        write_identifier(&mut out, text, text).unwrap();
+        out
+    }
+
+    fn contains(haystack: &str, needle: fmt::Arguments) -> bool {
+        let mut text = List::<u8, 64>::new();
+        text.write_fmt(needle).unwrap();
+        haystack.contains(str::from_utf8(text.as_slice()).unwrap())
+    }
+
+    fn variants<const N: usize>(values: [i64; N]) -> [TPair<FName, i64>; N] {
+        values.map(|value| TPair {
+            Key: unsafe { mem::zeroed() },
+            Value: value,
+        })
+    }
+
+    #[test]
+    fn enums_get_the_smallest_representation_of_all_their_variants() {
+        let representation = |variants: &[TPair<FName, i64>]| unsafe { get_enum_representation(variants) };
+
+        assert_eq!(representation(&variants([])), "u8");
+        assert_eq!(representation(&variants([0, 1, 255])), "u8");
+        assert_eq!(representation(&variants([0, 256])), "u32");
+        assert_eq!(representation(&variants([0, 65_536])), "u32");
+        assert_eq!(representation(&variants([0, 1 << 32])), "u64");
+        assert_eq!(representation(&variants([-1, 0, 1])), "i8");
+        assert_eq!(representation(&variants([-1, 255])), "i16");
+        assert_eq!(representation(&variants([i32::MIN.into(), 0])), "i32");
+        assert_eq!(representation(&variants([-1, i64::MAX])), "i64");
+    }
+
+    #[test]
+    fn try_from_accepts_only_known_discriminants() {
+        let mut out = List::<u8, 256>::new();
+        write_enum_try_from(&mut out, &variants([0, 2, 2, 5]), "u8").unwrap();
+        let out = str::from_utf8(out.as_slice()).unwrap();
+
+        assert!(out.contains("pub const fn try_from_u8(v: u8) -> Option<Self>"));
+        assert!(out.contains("0 | 2 | 5 => Some(Self(v)),"));
+        assert!(out.contains("_ => None,"));
+    }
+
+    #[test]
+    fn try_from_without_variants_is_always_none() {
+        let mut out = List::<u8, 256>::new();
+        write_enum_try_from(&mut out, &variants([]), "u8").unwrap();
+        let out = str::from_utf8(out.as_slice()).unwrap();
+
+        assert!(!out.contains("Some"));
+        assert!(out.contains("let _ = v;\n        None\n    }"));
+    }
+
+    #[test]
+    fn container_asserts_catch_a_wrong_element_size() {
+        // An `i32` array whose elements the engine says are 8 bytes.
+        let inner = mock::property("Counts", EClassCastFlags::CASTCLASS_FIntProperty, 0, 8);
+        let counts = mock::property("Counts", EClassCastFlags::CASTCLASS_FArrayProperty, 0, 0x10);
+        mock::set_subclass_field(counts, inner);
+
+        let structure = mock::script_struct("Inventory", mock::package("/Script/FSD"), 0x10, 8, &[counts]);
+        let out = generate(structure, GeneratorOptions { emit_container_asserts: true, ..GeneratorOptions::DEFAULT });
+
+        assert!(text(&out).contains("pub Counts: common::TArray<i32>,"));
+        assert!(text(&out).contains(
+            "const _: () = assert!(core::mem::size_of::<i32>() == 0x8, \"Inventory::Counts has the wrong element type\");"
+        ));
+    }
+
+    #[test]
+    fn only_single_line_inner_attributes_are_accepted() {
+        assert!(is_inner_attribute("#![allow(clippy::all)]"));
+        assert!(is_inner_attribute("#![no_std]"));
+        assert!(!is_inner_attribute("#[allow(dead_code)]"));
+        assert!(!is_inner_attribute("#![allow(dead_code)"));
+        assert!(!is_inner_attribute("#![allow(dead_code)]\npub mod evil;\n#![no_std]"));
+    }
+
+    #[test]
+    fn unknown_properties_become_opaque_bytes_of_their_size() {
+        let property = mock::property("Wavefunction", EClassCastFlags(1 << 62), 0, 0x18);
+        unsafe { (*(*property).base.ClassPrivate.cast_mut()).Name = mock::name("QuantumProperty") };
+
+        let structure = mock::script_struct("Particle", mock::package("/Script/FSD"), 0x18, 8, &[property]);
+        let mut out = Output::new(List::new());
+
+        unsafe {
+            let _generating = mock::generating();
+            let mut generator = StructGenerator::new(structure, (*structure).package(), &mut *out, false, GeneratorOptions::DEFAULT);
+            generator.generate().unwrap();
+            assert_eq!(generator.counts.opaque, 1);
+        }
+
+        assert!(text(&out).contains("pub Wavefunction: [u8; 24] /* WARN: UNKNOWN PROPERTY TYPE QuantumProperty, Id==4611686018427387904,"));
+    }
+
+    #[test]
+    fn skip_classes_match_an_object_by_name_or_class() {
+        let class = mock::class("EditorUtilityWidget", EClassCastFlags(0), ptr::null_mut());
+        let widget = mock::object("Widget_0", class.cast(), ptr::null_mut());
+        let skip_classes = ["Cooker", "EditorUtilityWidget", "Widget_0"];
+
+        assert_eq!(unsafe { skip_entry(&skip_classes, widget) }, Some(1));
+        assert_eq!(unsafe { skip_entry(&skip_classes[2..], widget) }, Some(0));
+        assert_eq!(unsafe { skip_entry(&skip_classes[..1], widget) }, None);
+    }
+
+    #[test]
+    fn array_fields_get_bounds_checked_accessors() {
+        let clips = mock::property("Clips", EClassCastFlags::CASTCLASS_FIntProperty, 0, 4);
+        unsafe { (*clips).ArrayDim = 3 };
+        let structure = mock::script_struct("WeaponState", mock::package("/Script/FSD"), 0xC, 4, &[clips]);
+
+        let out = generate(structure, GeneratorOptions { emit_array_accessors: true, ..GeneratorOptions::DEFAULT });
+        let out = text(&out);
+
+        assert!(out.contains("pub Clips: [i32; 3],"));
+        assert!(out.contains("pub fn Clips_at(&self, i: usize) -> Option<&i32> {\n        self.Clips.get(i)\n    }"));
+        assert!(out.contains("pub fn Clips_at_mut(&mut self, i: usize) -> Option<&mut i32> {\n        self.Clips.get_mut(i)\n    }"));
+    }
+
+    #[test]
+    fn only_the_root_class_gets_object_accessors() {
+        let package = mock::package("/Script/CoreUObject");
+        let object = mock::class("Object", EClassCastFlags(0), package.cast());
+        unsafe {
+            let object = &mut **object;
+            object.PropertiesSize = 0x28;
+            object.MinAlignment = 8;
+        }
+
+        let vector = mock::script_struct("Vector", package, 0xC, 4, &[]);
+        let accessor = "pub fn as_uobject(&self) -> *mut common::UObject {\n        self.as_uobject_const().cast_mut()\n    }";
+
+        assert!(text(&generate(object.cast(), GeneratorOptions::DEFAULT)).contains(accessor));
+        assert!(!text(&generate(vector, GeneratorOptions::DEFAULT)).contains("as_uobject"));
+    }
+
+    #[test]
+    fn bitfield_accessors_of_keyword_booleans_are_valid_methods() {
+        let structure = mock::script_struct(
+            "ItemFlags",
+            mock::package("/Script/FSD"),
+            1,
+            1,
+            &[mock::bitfield("type", 0, 1), mock::bitfield("bIs Used", 0, 2)],
+        );
+
+        let out = generate(structure, GeneratorOptions::DEFAULT);
+        let out = text(&out);
+
+        assert!(out.contains("pub unsafe fn r#type(&self) -> bool {\n        self.bitfield_at_0x0 & 1 != 0\n    }"));
+        assert!(out.contains("pub unsafe fn set_type(&mut self, enable: bool) {"));
+        assert!(out.contains("pub unsafe fn bIs_Used_replaced(&self) -> bool {\n        self.bitfield_at_0x0 & 2 != 0\n    }"));
+        assert!(out.contains("pub unsafe fn set_bIs_Used_replaced(&mut self, enable: bool) {"));
+    }
+
+    #[test]
+    fn reflected_impls_report_the_engine_size_and_alignment() {
+        let structure = mock::script_struct(
+            "WeaponState",
+            mock::package("/Script/FSD"),
+            5,
+            4,
+            &[mock::property("Ammo", EClassCastFlags::CASTCLASS_FIntProperty, 0, 4)],
+        );
+
+        let out = generate(structure, GeneratorOptions { emit_reflected: true, ..GeneratorOptions::DEFAULT });
+
+        assert!(text(&out).contains(
+            "impl common::Reflected for WeaponState {\n    \
+            const NAME: &'static str = \"WeaponState\";\n    \
+            const SIZE: usize = 0x5;\n    \
+            const ALIGN: usize = 4;\n}"
+        ));
+    }
+
+    fn enumeration<const N: usize>(name: &str, variants: [(&str, i64); N], options: GeneratorOptions) -> Output {
+        let class = mock::class("Enum", EClassCastFlags::CASTCLASS_UEnum, ptr::null_mut());
+        let enumeration = mock::object(name, class.cast(), mock::package("/Script/FSD").cast());
+        let variants = variants.map(|(name, value)| TPair { Key: mock::name(name), Value: value });
+        let mut out = Output::new(List::new());
+
+        unsafe {
+            let _generating = mock::generating();
+            let representation = get_enum_representation(&variants);
+            write_enum(&mut *out, enumeration.cast(), &variants, representation, &options).unwrap();
+        }
+
+        out
+    }
+
+    #[test]
+    fn single_variant_enums_keep_their_type_but_lose_the_extra_impls() {
+        let options = GeneratorOptions {
+            trim_single_variant_enums: true,
+            emit_enum_deref: true,
+            emit_enum_try_from: true,
+            emit_reflected: true,
+            ..GeneratorOptions::DEFAULT
+        };
+
+        let single = enumeration("EOnly", [("EOnly::Value", 0)], options);
+        let single = text(&single);
+
+        assert!(single.contains("pub struct EOnly(u8);"));
+        assert!(single.contains("pub const Value: Self = Self(0);"));
+        assert!(single.contains("pub const VARIANT_COUNT: usize = 1;"));
+        assert!(!single.contains("try_from_u8"));
+        assert!(!single.contains("impl core::ops::Deref"));
+        assert!(!single.contains("impl common::Reflected"));
+
+        let pair = enumeration("EPair", [("EPair::A", 0), ("EPair::B", 1)], options);
+        let pair = text(&pair);
+
+        assert!(pair.contains("try_from_u8"));
+        assert!(pair.contains("impl core::ops::Deref for EPair"));
+        assert!(pair.contains("impl common::Reflected for EPair"));
+    }
+
+    #[test]
+    fn classes_get_a_checked_downcast_and_structs_do_not() {
+        let package = mock::package("/Script/FSD");
+        let weapon = mock::class("AmmoDrivenWeapon", EClassCastFlags(0), package.cast());
+        unsafe {
+            let weapon = &mut **weapon;
+            weapon.PropertiesSize = 0x28;
+            weapon.MinAlignment = 8;
+        }
+
+        let state = mock::script_struct("WeaponState", package, 4, 4, &[]);
+
+        let out = generate(weapon.cast(), GeneratorOptions::DEFAULT);
+        let out = text(&out);
+
+        assert!(out.contains("impl common::StaticClass for AmmoDrivenWeapon {"));
+        assert!(out.contains("CLASS = (*common::GUObjectArray).find_class(\"Class /Script/FSD.AmmoDrivenWeapon\");"));
+        assert!(out.contains(
+            "pub unsafe fn from_object(object: *mut common::UObject) -> Option<*mut Self> {\n        \
+            common::try_cast::<Self>(object)\n    }"
+        ));
+
+        assert!(!text(&generate(state, GeneratorOptions::DEFAULT)).contains("from_object"));
+    }
+
+    #[test]
+    fn variant_count_matches_the_emitted_variants() {
+        let variants = [("EGrenadeType::Impact", 0), ("EGrenadeType::Sticky", 1), ("EGrenadeType::Sticky", 1), ("Self", 3)];
+        let out = enumeration("EGrenadeType", variants, GeneratorOptions::DEFAULT);
+        let out = text(&out);
+
+        assert!(out.contains("pub const VARIANT_COUNT: usize = 4;"));
+        assert_eq!(out.matches(": Self = Self(").count(), 4);
+        assert!(out.contains("pub const SelfVariant: Self = Self(3);"));
+    }
+
+    #[test]
+    fn property_flags_become_constants_named_after_their_field() {
+        let ammo = mock::property("Ammo", EClassCastFlags::CASTCLASS_FIntProperty, 0, 4);
+        let flags = EPropertyFlags::CPF_Net.0 | EPropertyFlags::CPF_Transient.0;
+        unsafe { (*ammo).PropertyFlags = EPropertyFlags(flags) };
+        let structure = mock::script_struct("WeaponState", mock::package("/Script/FSD"), 4, 4, &[ammo]);
+
+        let out = generate(structure, GeneratorOptions { emit_property_flags: true, ..GeneratorOptions::DEFAULT });
+
+        assert!(text(&out).contains(
+            "#[allow(non_upper_case_globals)]\nimpl WeaponState {\n    \
+            pub const Ammo_FLAGS: common::EPropertyFlags = common::EPropertyFlags(0x2020);\n}"
+        ));
+    }
+
+    #[test]
+    fn bitfields_of_odd_sizes_are_stored_as_bytes() {
+        let structure = mock::script_struct(
+            "PackedFlags",
+            mock::package("/Script/FSD"),
+            3,
+            1,
+            &[mock::wide_bitfield("bIsLow", 0, 3, 0, 1), mock::wide_bitfield("bIsHigh", 0, 3, 2, 0x80)],
+        );
+
+        let out = generate(structure, GeneratorOptions::DEFAULT);
+        let out = text(&out);
+
+        assert!(out.contains("pub bitfield_at_0x0: [u8; 3],"));
+        assert!(out.contains("pub unsafe fn bIsLow(&self) -> bool {\n        self.bitfield_at_0x0[0] & 1 != 0\n    }"));
+        assert!(out.contains("pub unsafe fn bIsHigh(&self) -> bool {\n        self.bitfield_at_0x0[2] & 128 != 0\n    }"));
+        assert!(out.contains("self.bitfield_at_0x0[2] |= 128;"));
+        assert!(out.contains("self.bitfield_at_0x0[2] &= !128;"));
+    }
+
+    #[test]
+    fn parameter_structs_assert_the_function_size() {
+        let weapon = mock::class("Weapon", EClassCastFlags(0), mock::package("/Script/FSD").cast());
+
+        let slot = mock::property("Slot", EClassCastFlags::CASTCLASS_FIntProperty, 0, 4);
+        let heat = mock::property("ReturnValue", EClassCastFlags::CASTCLASS_FFloatProperty, 4, 4);
+        unsafe {
+            (*slot).PropertyFlags = EPropertyFlags::CPF_Parm;
+            (*heat).PropertyFlags = EPropertyFlags(EPropertyFlags::CPF_Parm.0 | EPropertyFlags::CPF_ReturnParm.0);
+        }
+
+        let get_heat = mock::function("GetHeat", weapon.cast(), &[slot, heat]);
+
+        unsafe {
+            (*get_heat.cast::<UStruct>()).PropertiesSize = 8;
+
+            let weapon = &mut **weapon;
+            weapon.PropertiesSize = 0x28;
+            weapon.MinAlignment = 8;
+            weapon.Children = get_heat.cast();
+        }
+
+        let check = "assert!(core::mem::size_of::<Parameters>() == (0x8 + align - 1) / align * align, \"wrong parameter layout\");";
+
+        let out = generate(weapon.cast(), GeneratorOptions { emit_params_asserts: true, ..GeneratorOptions::DEFAULT });
+        assert!(text(&out).contains(check));
+
+        let out = generate(weapon.cast(), GeneratorOptions::DEFAULT);
+        assert!(text(&out).contains("pub unsafe fn GetHeat(&mut self, Slot: i32, ) -> f32 {"));
+        assert!(!text(&out).contains(check));
+    }
+
+    #[test]
+    fn layout_strings_list_each_field_and_bitfield_bool() {
+        let clips = mock::property("Clips", EClassCastFlags::CASTCLASS_FIntProperty, 8, 4);
+        unsafe { (*clips).ArrayDim = 2 };
+
+        let structure = mock::script_struct(
+            "WeaponState",
+            mock::package("/Script/FSD"),
+            0x10,
+            4,
+            &[
+                mock::property("Ammo", EClassCastFlags::CASTCLASS_FIntProperty, 0, 4),
+                mock::bitfield("bIsReloading", 4, 1),
+                mock::bitfield("bIsOverheated", 4, 2),
+                clips,
+            ],
+        );
+
+        let out = generate(structure, GeneratorOptions { emit_layout_strings: true, ..GeneratorOptions::DEFAULT });
+
+        assert!(text(&out).contains(
+            "impl WeaponState {\n    \
+            pub const LAYOUT: &'static str = \"0x0:0x4:Ammo;0x4:0x1:bIsReloading;0x4:0x1:bIsOverheated;0x8:0x8:Clips\";\n}"
+        ));
+    }
+
+    #[test]
+    fn object_arrays_get_iterators_over_their_non_null_pointers() {
+        let package = mock::package("/Script/FSD");
+        let item = mock::class("Item", EClassCastFlags(0), package.cast());
+
+        let inner = mock::property("Items", EClassCastFlags::CASTCLASS_FObjectProperty, 0, 8);
+        mock::set_subclass_field(inner, item);
+        let items = mock::property("Items", EClassCastFlags::CASTCLASS_FArrayProperty, 0, 0x10);
+        mock::set_subclass_field(items, inner);
+
+        let structure = mock::script_struct("Inventory", package, 0x10, 8, &[items]);
+        let out = generate(structure, GeneratorOptions { emit_pointer_iterators: true, ..GeneratorOptions::DEFAULT });
+
+        assert!(text(&out).contains(
+            "pub fn iter_Items(&self) -> impl Iterator<Item = *mut Item> + '_ {\n        \
+            self.Items.iter().copied().filter(|p| !p.is_null())\n    }"
+        ));
+    }
+
+    #[test]
+    fn tail_padding_is_noted_and_not_counted_twice_by_derived_structs() {
+        let package = mock::package("/Script/FSD");
+        let base = mock::script_struct(
+            "WeaponState",
+            package,
+            5,
+            4,
+            &[
+                mock::property("Ammo", EClassCastFlags::CASTCLASS_FIntProperty, 0, 4),
+                mock::property("Mode", EClassCastFlags::CASTCLASS_FByteProperty, 4, 1),
+            ],
+        );
+
+        let derived = mock::script_struct("GrenadeState", package, 0xC, 4, &[mock::property("Fuse", EClassCastFlags::CASTCLASS_FFloatProperty, 8, 4)]);
+        unsafe { (*derived).SuperStruct = base };
+
+        let note = "    // NOTE: Rust rounds this structure up to 0x8 bytes, a multiple of its alignment. The last 3 bytes are tail padding the engine doesn't count.\n";
+        assert!(text(&generate(base, GeneratorOptions::DEFAULT)).contains(note));
+
+        let out = generate(derived, GeneratorOptions::DEFAULT);
+        let out = text(&out);
+        assert!(out.contains("// offset: 0x8, size: 0x4\n    pub Fuse: f32,"));
+        assert!(!out.contains("WARN"));
+        assert!(!out.contains("NOTE"));
+    }
+
+    #[test]
+    fn package_files_land_where_lib_rs_declares_them() {
+        let path = |options: &GeneratorOptions, file| {
+            let path = package_path(package_dir(options), "FSD", file).unwrap();
+            String::from_utf8(path.as_slice().to_vec()).unwrap()
+        };
+
+        let declaration = |options: &GeneratorOptions| {
+            let mut out = List::<u8, 64>::new();
+            write_package_module(&mut out, options, "FSD").unwrap();
+            String::from_utf8(out.as_slice().to_vec()).unwrap()
+        };
+
+        let flat = GeneratorOptions::DEFAULT;
+        assert!(path(&flat, format_args!(".rs")).ends_with("/src/FSD.rs\0"));
+        assert_eq!(declaration(&flat), "pub mod FSD;\n");
+
+        let nested = GeneratorOptions { packages_subdirectory: true, ..GeneratorOptions::DEFAULT };
+        assert!(path(&nested, format_args!(".rs")).ends_with("/src/packages/FSD.rs\0"));
+        assert_eq!(declaration(&nested), "#[path = \"packages/FSD.rs\"]\npub mod FSD;\n");
+
+        let split = GeneratorOptions { split_packages: true, ..nested };
+        assert!(path(&split, format_args!("/mod.rs")).ends_with("/src/packages/FSD/mod.rs\0"));
+        assert_eq!(declaration(&split), "#[path = \"packages/FSD/mod.rs\"]\npub mod FSD;\n");
+    }
+
+    #[test]
+    fn class_table_entries_name_the_generated_static_class() {
+        let controller = mock::class("FSDPlayerController", EClassCastFlags(0), mock::package("/Script/FSD").cast());
+        let pickaxe = mock::class("BP_Pickaxe_C", EClassCastFlags(0), mock::package("/Game/BP_Pickaxe").cast());
+
+        let mut out = List::<u8, 256>::new();
+
+        unsafe {
+            write_class_table_entry(&mut out, controller.cast(), false).unwrap();
+            write_class_table_entry(&mut out, pickaxe.cast(), true).unwrap();
+        }
+
+        assert_eq!(
+            str::from_utf8(out.as_slice()).unwrap(),
+            "    (\"FSDPlayerController\", crate::FSD::FSDPlayerController::static_class),\n    \
+            (\"BP_Pickaxe_C\", crate::blueprint_generated::BP_Pickaxe_C::static_class),\n",
+        );
+    }
+
+    #[test]
+    fn out_of_order_properties_are_warned_about() {
+        let structure = mock::script_struct(
+            "WeaponState",
+            mock::package("/Script/FSD"),
+            0xC,
+            4,
+            &[
+                mock::property("Heat", EClassCastFlags::CASTCLASS_FFloatProperty, 8, 4),
+                mock::property("Ammo", EClassCastFlags::CASTCLASS_FIntProperty, 0, 4),
+            ],
+        );
+
+        let mut out = Output::new(List::new());
+
+        let warnings = unsafe {
+            let _generating = mock::generating();
+            let mut generator = StructGenerator::new(structure, (*structure).package(), &mut *out, false, GeneratorOptions::DEFAULT);
+            generator.generate().unwrap();
+            generator.counts.warnings
+        };
+
+        assert!(text(&out).contains("    // WARNING: Property \"Ammo\" at 0x0 comes after \"Heat\" at 0x8. Fields are out of order.\n"));
+        assert!(warnings >= 1);
+
+        // In order, bitfields sharing an offset included.
+        let structure = mock::script_struct(
+            "WeaponState",
+            mock::package("/Script/FSD"),
+            8,
+            4,
+            &[
+                mock::property("Ammo", EClassCastFlags::CASTCLASS_FIntProperty, 0, 4),
+                mock::bitfield("bIsReloading", 4, 1),
+                mock::bitfield("bIsOverheated", 4, 2),
+            ],
+        );
+
+        assert!(!text(&generate(structure, GeneratorOptions::DEFAULT)).contains("out of order"));
+    }
+
+    #[test]
+    fn enums_are_copyable_newtypes_with_a_value_accessor() {
+        let out = enumeration("EAmmoType", [("EAmmoType::Primary", 0), ("EAmmoType::Secondary", 1)], GeneratorOptions::DEFAULT);
+
+        assert_eq!(
+            text(&out),
+            "// Enum /Script/FSD.EAmmoType\n\
+            #[repr(transparent)]\n\
+            #[derive(Copy, Clone, PartialEq, Eq)]\n\
+            pub struct EAmmoType(u8);\n\
+            \n\
+            #[allow(non_upper_case_globals)]\n\
+            impl EAmmoType {\n    \
+                pub const Primary: Self = Self(0);\n    \
+                pub const Secondary: Self = Self(1);\n\
+            \n    \
+                pub const VARIANT_COUNT: usize = 2;\n\
+            \n    \
+                pub const fn value(self) -> u8 {\n        \
+                    self.0\n    \
+                }\n\
+            }\n\n",
+        );
+    }
+
+    #[test]
+    fn static_arrays_take_up_their_whole_size() {
+        let spread = mock::property("Spread", EClassCastFlags::CASTCLASS_FFloatProperty, 4, 4);
+        unsafe { (*spread).ArrayDim = 8 };
+
+        let structure = mock::script_struct(
+            "WeaponState",
+            mock::package("/Script/FSD"),
+            0x28,
+            4,
+            &[
+                mock::property("Ammo", EClassCastFlags::CASTCLASS_FIntProperty, 0, 4),
+                spread,
+                mock::property("Heat", EClassCastFlags::CASTCLASS_FFloatProperty, 0x24, 4),
+            ],
+        );
+
+        let out = generate(structure, GeneratorOptions::DEFAULT);
+        let out = text(&out);
+
+        assert!(out.contains("    // offset: 0x4, size: 0x20\n    pub Spread: [f32; 8],\n"));
+        assert!(out.contains("    // offset: 0x24, size: 0x4\n    pub Heat: f32,\n"));
+        assert!(!out.contains("pad_at"));
+        assert!(!out.contains("WARN"));
+    }
+
+    #[test]
+    fn offset_asserts_skip_bitfields_and_padding() {
+        let structure = mock::script_struct(
+            "WeaponState",
+            mock::package("/Script/FSD"),
+            0xC,
+            4,
+            &[
+                mock::property("Ammo", EClassCastFlags::CASTCLASS_FIntProperty, 0, 4),
+                mock::bitfield("bIsReloading", 4, 1),
+                mock::property("Heat", EClassCastFlags::CASTCLASS_FFloatProperty, 8, 4),
+            ],
+        );
+
+        let out = generate(structure, GeneratorOptions { emit_offset_asserts: true, ..GeneratorOptions::DEFAULT });
+
+        assert!(text(&out).contains(
+            "#[cfg(test)]\nconst _: () = {\n    \
+            assert!(core::mem::offset_of!(WeaponState, Ammo) == 0x0, \"WeaponState::Ammo is at the wrong offset\");\n    \
+            assert!(core::mem::offset_of!(WeaponState, Heat) == 0x8, \"WeaponState::Heat is at the wrong offset\");\n    \
+            assert!(core::mem::size_of::<WeaponState>() == 0xc, \"WeaponState has the wrong size\");\n};\n"
+        ));
+    }
+
+    #[test]
+    fn offset_asserts_allow_deprecated_fields_with_markers() {
+        let clip = mock::property("Clip_DEPRECATED", EClassCastFlags::CASTCLASS_FIntProperty, 0, 4);
+        let structure = mock::script_struct("WeaponState", mock::package("/Script/FSD"), 4, 4, &[clip]);
+
+        let options = GeneratorOptions {
+            emit_offset_asserts: true,
+            emit_deprecated_markers: true,
+            ..GeneratorOptions::DEFAULT
+        };
+
+        assert!(text(&generate(structure, options)).contains("#[cfg(test)]\n#[allow(deprecated)]\nconst _: () = {"));
+    }
+
+    #[test]
+    fn editor_only_fields_are_gated_behind_the_editor_feature() {
+        let note = mock::property("DevNote", EClassCastFlags::CASTCLASS_FIntProperty, 4, 4);
+        unsafe { (*note).PropertyFlags = EPropertyFlags::CPF_EditorOnly };
+
+        let structure = mock::script_struct(
+            "WeaponState",
+            mock::package("/Script/FSD"),
+            0xC,
+            4,
+            &[
+                mock::property("Ammo", EClassCastFlags::CASTCLASS_FIntProperty, 0, 4),
+                note,
+                mock::property("Heat", EClassCastFlags::CASTCLASS_FFloatProperty, 8, 4),
+            ],
+        );
+
+        let options = GeneratorOptions {
+            gate_editor_only_fields: true,
+            emit_offset_asserts: true,
+            ..GeneratorOptions::DEFAULT
+        };
+
+        let out = generate(structure, options);
+        let out = text(&out);
+
+        assert!(out.contains("    #[cfg(feature = \"editor\")]\n    // offset: 0x4, size: 0x4\n    pub DevNote: i32,\n"));
+        assert!(out.contains("    // offset: 0x8, size: 0x4\n    pub Heat: f32,\n"));
+        assert_eq!(out.matches("#[cfg(feature = \"editor\")]\n").count(), 1);
+        assert!(out.contains(
+            "    #[cfg(not(feature = \"editor\"))] assert!(core::mem::offset_of!(WeaponState, Heat) == 0x4, \"WeaponState::Heat is at the wrong offset\");\n"
+        ));
+        assert!(out.contains("    #[cfg(not(feature = \"editor\"))] assert!(core::mem::size_of::<WeaponState>() == 0x8, \"WeaponState has the wrong size\");\n"));
+    }
+
+    #[test]
+    fn keywords_become_raw_identifiers() {
+        assert_eq!(identifier("type").as_slice(), b"r#type");
+        assert_eq!(identifier("async").as_slice(), b"r#async");
+        assert_eq!(identifier("Self").as_slice(), b"Self_");
+        assert_eq!(identifier("Health").as_slice(), b"Health");
+    }
+
+    #[test]
+    fn accessors_of_keyword_fields_have_plain_names() {
+        for (name, field) in [("type", "r#type"), ("Self", "Self_"), ("async", "r#async")] {
+            let mut out = List::<u8, 512>::new();
+            write_array_accessors(&mut out, "", name, field, "u8").unwrap();
+            write_pointer_iterator(&mut out, "", name, field, "*mut UObject").unwrap();
+            let out = str::from_utf8(out.as_slice()).unwrap();
+
+            assert!(contains(out, format_args!("pub fn {name}_at(&self")));
+            assert!(contains(out, format_args!("pub fn {name}_at_mut(&mut self")));
+            assert!(contains(out, format_args!("pub fn iter_{name}(&self)")));
+            assert!(contains(out, format_args!("self.{field}.get(i)")));
+            assert!(contains(out, format_args!("self.{field}.iter()")));
+        }
+    }
+
+    #[test]
+    fn generate_packages_picks_the_objects_of_the_named_packages() {
+        let class = mock::object("Class", ptr::null(), ptr::null_mut());
+        let fsd = mock::object("/Script/FSD", class, ptr::null_mut());
+        let character = mock::object("PlayerCharacter", class, fsd);
+        let engine = mock::object("/Script/Engine", class, ptr::null_mut());
+        let actor = mock::object("Actor", class, engine);
+        let core = mock::object("/Script/CoreUObject", class, ptr::null_mut());
+        let object = mock::object("Object", class, core);
+
+        let objects = [fsd, character, engine, actor, core, object];
+        let memory = Snapshot::new(&objects);
+        let mut picked = List::<*mut UObject, 8>::new();
+
+        for &object in &objects {
+            if unsafe { is_in_packages(&memory, object, &["FSD", "Engine"]) }.unwrap() {
+                picked.push(object).unwrap();
+            }
+        }
+
+        assert_eq!(picked.as_slice(), [fsd, character, engine, actor]);
+    }
+
+    #[test]
+    fn generate_packages_skips_unreadable_objects() {
+        let package = mock::object("/Script/FSD", ptr::null(), ptr::null_mut());
+        let memory = Snapshot::new(&[]);
+
+        // The snapshot can't read a null class.
+        let result = unsafe { is_in_packages(&memory, package, &["FSD"]) };
+        assert!(matches!(result, Err(Error::ObjectFaulted(0))));
+    }
+
+    #[test]
+    fn generate_struct_by_name_prints_only_that_struct() {
+        let package = mock::package("/Script/FSD");
+        let ammo = mock::property("Ammo", EClassCastFlags::CASTCLASS_FIntProperty, 0, 4);
+        let state = mock::script_struct("WeaponState", package, 4, 4, &[ammo]);
+        let other = mock::script_struct("Loadout", package, 4, 4, &[]);
+
+        let objects = [package.cast(), other.cast(), state.cast()];
+        let memory = Snapshot::new(&objects);
+        let mut out = Output::new(List::new());
+        let _generating = mock::generating();
+
+        unsafe { generate_struct_by_name(&memory, GeneratorOptions::DEFAULT, "FSD", "WeaponState", &mut *out) }.unwrap();
+
+        assert!(text(&out).starts_with("// ScriptStruct /Script/FSD.WeaponState is 0x4 bytes."));
+        assert!(text(&out).contains("pub Ammo: i32,"));
+        assert!(!text(&out).contains("Loadout"));
+
+        let missing = unsafe { generate_struct_by_name(&memory, GeneratorOptions::DEFAULT, "Engine", "WeaponState", &mut *out) };
+        assert!(matches!(missing, Err(Error::StructNotFound)));
+    }
+
+    #[test]
+    fn only_fields_after_editor_only_ones_get_a_second_layout() {
+        assert!(layouts(0x10, 0, false).eq([("", 0x10)]));
+        assert!(layouts(0x10, 0, true).eq([(EDITOR_CFG, 0x10)]));
+        assert!(layouts(0x10, 4, true).eq([(EDITOR_CFG, 0x10)]));
+        assert!(layouts(0x18, 8, false).eq([(EDITOR_CFG, 0x18), (RUNTIME_CFG, 0x10)]));
+    }
+
+    #[test]
+    fn accessor_impls_allow_deprecated_fields_with_markers() {
+        let mut out = List::<u8, 64>::new();
+        write_accessor_impl(&mut out, "Weapon", true).unwrap();
+        assert_eq!(out.as_slice(), b"#[allow(deprecated)]\nimpl Weapon {\n");
+
+        let mut out = List::<u8, 64>::new();
+        write_accessor_impl(&mut out, "Weapon", false).unwrap();
+        assert_eq!(out.as_slice(), b"impl Weapon {\n");
+    }
+
+    // Everything above checks the text; this has rustc check that the text is
+    // Rust, layout asserts included.
+    #[test]
+    fn generated_structs_and_enums_compile() {
+        use std::{env, fs, process};
+
+        let structure = mock::script_struct(
+            "WeaponState",
+            mock::package("/Script/FSD"),
+            0xC,
+            4,
+            &[
+                mock::property("Ammo", EClassCastFlags::CASTCLASS_FIntProperty, 0, 4),
+                mock::property("Heat", EClassCastFlags::CASTCLASS_FFloatProperty, 4, 4),
+                mock::bitfield("bIsReloading", 8, 1),
+                mock::bitfield("bIsOverheated", 8, 2),
+            ],
+        );
+
+        let options = GeneratorOptions { emit_offset_asserts: true, emit_enum_try_from: true, ..GeneratorOptions::DEFAULT };
+        let enumeration = enumeration("EAmmoType", [("EAmmoType::Primary", 0), ("EAmmoType::Secondary", 1)], options);
+
+        let mut source = String::from("#![no_std]\n#![allow(dead_code, non_camel_case_types, non_snake_case)]\n\npub mod FSD {\n");
+        source.push_str(text(&generate(structure, options)));
+        source.push_str(text(&enumeration));
+        source.push_str("}\n");
+
+        let dir = env::temp_dir().join(std::format!("sdk_gen_compile_{}", process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        fs::write(dir.join("lib.rs"), source).unwrap();
+
+        // `--cfg test` so that the `#[cfg(test)]` asserts are evaluated too.
+        let output = process::Command::new(env::var_os("RUSTC").unwrap_or_else(|| "rustc".into()))
+            .args(["--edition", "2021", "--crate-type", "lib", "--crate-name", "sdk", "--cfg", "test", "--emit", "metadata", "-D", "warnings"])
+            .arg("--out-dir")
+            .arg(&dir)
+            .arg(dir.join("lib.rs"))
+            .output()
+            .unwrap();
+
+        let _ = fs::remove_dir_all(&dir);
+        assert!(output.status.success(), "{}", String::from_utf8_lossy(&output.stderr));
+    }
+}
@@ -0,0 +1,115 @@
+use super::intern::fnv1a;
+use super::Error;
+use crate::game::{FBoolProperty, FProperty, PropertyDisplayable};
+use common::{EClassCastFlags, List, UStruct};
+use core::fmt::{self, Write};
+use core::ptr;
+
+// Struct layouts we've seen, keyed by a hash of the rendered layout. Only the
+// first struct with each layout is kept.
+const CAPACITY: usize = 1 << 15;
+
+// Longest rendered layout we'll compare. Structs with longer ones never get
+// conversions.
+const MAX_LAYOUT_LEN: usize = 8192;
+
+// Deepest inheritance chain we'll flatten.
+const MAX_DEPTH: usize = 32;
+
+#[derive(Copy, Clone)]
+struct Entry {
+    hash: u64,
+    structure: *const UStruct,
+}
+
+static mut ENTRIES: [Option<Entry>; CAPACITY] = [None; CAPACITY];
+
+// Forgets every layout, for a new `Generator`.
+pub unsafe fn reset() {
+    (*ptr::addr_of_mut!(ENTRIES)).fill(None);
+}
+
+// Returns the first struct seen whose layout is identical to `structure`'s,
+// or remembers `structure` if it's the first with its layout.
+//
+// Two layouts are identical when the structs have the same size and alignment
+// and, after flattening inheritance, the same sequence of field names, field
+// offsets, and fully qualified field types (plus bit masks for bitfields).
+// Flattening is what lets e.g. `Vector_NetQuantize`, which only inherits from
+// `Vector`, match `Vector`. Anything short of that, even a renamed field, is
+// treated as different.
+pub unsafe fn find_identical(structure: *const UStruct) -> Result<Option<*const UStruct>, Error> {
+    let mut layout = List::<u8, MAX_LAYOUT_LEN>::new();
+
+    if write_layout(&mut layout, structure).is_err() {
+        return Ok(None);
+    }
+
+    let hash = fnv1a(layout.as_slice());
+    let mut slot = (hash as usize) % CAPACITY;
+
+    for _ in 0..CAPACITY {
+        let entry = match (*ptr::addr_of_mut!(ENTRIES)).get_mut(slot) {
+            Some(entry) => entry,
+            None => break,
+        };
+
+        match *entry {
+            None => {
+                *entry = Some(Entry { hash, structure });
+                return Ok(None);
+            }
+
+            Some(e) if e.hash == hash && is_same_layout(e.structure, layout.as_slice()) => {
+                return Ok(Some(e.structure));
+            }
+
+            Some(_) => slot = (slot + 1) % CAPACITY,
+        }
+    }
+
+    Err(Error::MaxConversions)
+}
+
+unsafe fn is_same_layout(structure: *const UStruct, layout: &[u8]) -> bool {
+    let mut existing = List::<u8, MAX_LAYOUT_LEN>::new();
+    write_layout(&mut existing, structure).is_ok() && existing.as_slice() == layout
+}
+
+unsafe fn write_layout(out: &mut impl Write, structure: *const UStruct) -> fmt::Result {
+    write!(out, "{} {};", (*structure).PropertiesSize, (*structure).MinAlignment)?;
+
+    let mut chain = List::<*const UStruct, MAX_DEPTH>::new();
+    let mut current = structure;
+
+    while !current.is_null() {
+        chain.push(current).map_err(|_| fmt::Error)?;
+        current = (*current).SuperStruct;
+    }
+
+    // Bases first, in the order their fields are laid out.
+    for &current in chain.as_slice().iter().rev() {
+        let mut property = (*current).ChildProperties.cast::<FProperty>();
+
+        while !property.is_null() {
+            // A null package qualifies every type, so that the same type
+            // renders the same no matter which package mentions it.
+            write!(
+                out,
+                "{} {} {}",
+                (*property).base.NamePrivate,
+                (*property).Offset,
+                PropertyDisplayable::new(property, ptr::null(), false),
+            )?;
+
+            if (*property).is(EClassCastFlags::CASTCLASS_FBoolProperty) {
+                write!(out, " {}", (*property.cast::<FBoolProperty>()).ByteMask)?;
+            }
+
+            out.write_char(';')?;
+            property = (*property).base.Next.cast();
+        }
+    }
+
+    Ok(())
+}
@@ -0,0 +1,106 @@
+use super::{package_module_name, CleanedName, Error};
+use common::{List, UStruct};
+use core::cmp::Ordering;
+use core::fmt::Write;
+use core::ptr;
+
+// The structs that `FunctionFeatures::PerStruct` gives a feature of their own,
+// and whether each is Blueprint-generated. `Generator::finish()` declares them
+// sorted, so that `Cargo.toml` doesn't change with the order of
+// `GUObjectArray`.
+const MAX_STRUCTS: usize = 1 << 16;
+
+static mut STRUCTS: List<(*const UStruct, bool), MAX_STRUCTS> = List::new();
+
+// Forgets every struct, for a new `Generator`.
+pub unsafe fn reset() {
+    (*ptr::addr_of_mut!(STRUCTS)).clear();
+}
+
+pub unsafe fn add(structure: *const UStruct, is_blueprint_generated: bool) -> Result<(), Error> {
+    (*ptr::addr_of_mut!(STRUCTS))
+        .push((structure, is_blueprint_generated))
+        .map_err(|_| Error::MaxFeatures)
+}
+
+// Writes `fn_{module}_{struct} = []` for each struct, sorted by feature name.
+// A package in `unchanged_packages` can also have had types generated into it,
+// so a struct can be here twice, but Cargo rejects a feature declared twice.
+pub unsafe fn write(mut out: impl Write) -> Result<(), Error> {
+    let structs = (*ptr::addr_of_mut!(STRUCTS)).as_mut_slice();
+    structs.sort_unstable_by(|&a, &b| compare(a, b));
+
+    let mut previous = None;
+
+    for &entry in structs.iter() {
+        if previous.is_none_or(|p| compare(p, entry) != Ordering::Equal) {
+            writeln!(out, "fn_{} = []", str_of(&name(entry)))?;
+        }
+
+        previous = Some(entry);
+    }
+
+    Ok(())
+}
+
+fn compare(a: (*const UStruct, bool), b: (*const UStruct, bool)) -> Ordering {
+    name(a).as_slice().cmp(name(b).as_slice())
+}
+
+// What follows `fn_` in the feature name. Too long a name is cut short, which
+// only happens to names that are too long for a Rust identifier anyway.
+fn name((structure, is_blueprint_generated): (*const UStruct, bool)) -> List<u8, 512> {
+    let mut name = List::new();
+
+    unsafe {
+        let _ = write!(
+            name,
+            "{}_{}",
+            package_module_name((*structure).package(), is_blueprint_generated),
+            CleanedName::new((&*structure).NamePrivate),
+        );
+    }
+
+    name
+}
+
+fn str_of(name: &List<u8, 512>) -> &str {
+    // SAFETY: `List`'s `Write` only ever appends whole `str`s, and both parts
+    // of the name are ASCII.
+    unsafe { core::str::from_utf8_unchecked(name.as_slice()) }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::mock;
+    use core::str;
+
+    #[test]
+    fn struct_features_are_sorted_and_unique() {
+        let class = mock::object("Class", ptr::null(), ptr::null_mut());
+        let fsd = mock::object("/Script/FSD", class, ptr::null_mut());
+        let engine = mock::object("/Script/Engine", class, ptr::null_mut());
+
+        let zed = mock::object("Zed", class, fsd);
+        let structs = [zed, mock::object("A-B", class, fsd), mock::object("Actor", class, engine), zed];
+
+        let mut out = List::<u8, 256>::new();
+
+        unsafe {
+            let _generating = mock::generating();
+            reset();
+
+            for structure in structs {
+                add(structure.cast(), false).unwrap();
+            }
+
+            write(&mut out).unwrap();
+        }
+
+        assert_eq!(
+            str::from_utf8(out.as_slice()).unwrap(),
+            "fn_Engine_Actor = []\nfn_FSD_A_B_replaced = []\nfn_FSD_Zed = []\n",
+        );
+    }
+}
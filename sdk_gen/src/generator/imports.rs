@@ -0,0 +1,103 @@
+use super::intern::fnv1a;
+use common::{UObject, UPackage};
+use core::ptr;
+
+// Two tables keyed by a package and a type name:
+// * `TYPES` holds every struct, class, and enum that goes into a package
+//   module, so that we never import a name over one that's defined locally.
+// * `IMPORTS` holds the types each package module imports by their short
+//   name, so that a name only ever refers to one type per module.
+// Open addressing like in `intern`. When a table fills up, references simply
+// stay fully qualified.
+const CAPACITY: usize = 1 << 18;
+
+#[derive(Copy, Clone)]
+struct Entry {
+    package: *const UPackage,
+    hash: u64,
+    typ: *const UObject,
+}
+
+static mut TYPES: [Option<Entry>; CAPACITY] = [None; CAPACITY];
+static mut IMPORTS: [Option<Entry>; CAPACITY] = [None; CAPACITY];
+
+// Forgets every type and import, for a new `Generator`.
+pub unsafe fn reset() {
+    (*ptr::addr_of_mut!(TYPES)).fill(None);
+    (*ptr::addr_of_mut!(IMPORTS)).fill(None);
+}
+
+// Names the prelude already gives every module. Importing a type over one of
+// these would break generated code that means the prelude's.
+const PRELUDE: &[&str] = &[
+    "AsMut", "AsRef", "Clone", "Copy", "Default", "DoubleEndedIterator", "Drop", "Eq", "Err",
+    "ExactSizeIterator", "Extend", "Fn", "FnMut", "FnOnce", "From", "Into", "IntoIterator",
+    "Iterator", "None", "Ok", "Option", "Ord", "PartialEq", "PartialOrd", "Result", "Send",
+    "Sized", "Some", "Sync", "Unpin",
+];
+
+pub unsafe fn add_type(typ: *const UObject) {
+    let _ = find_or_insert(&mut *ptr::addr_of_mut!(TYPES), (*typ).package(), typ);
+}
+
+// Whether `typ` may be named by its short name in `package`'s module. The
+// first call that says yes for a name records the import.
+pub unsafe fn try_import(package: *const UPackage, typ: *const UObject) -> bool {
+    let name = (*typ).name();
+
+    if PRELUDE.contains(&name) || find(&*ptr::addr_of!(TYPES), package, name).is_some() {
+        return false;
+    }
+
+    find_or_insert(&mut *ptr::addr_of_mut!(IMPORTS), package, typ) == Some(typ)
+}
+
+// Calls `f` with each recorded import and the package that imports it.
+pub unsafe fn for_each(mut f: impl FnMut(*const UPackage, *const UObject)) {
+    for entry in (*ptr::addr_of!(IMPORTS)).iter().flatten() {
+        f(entry.package, entry.typ);
+    }
+}
+
+unsafe fn find(table: &[Option<Entry>; CAPACITY], package: *const UPackage, name: &str) -> Option<*const UObject> {
+    let hash = fnv1a(name.as_bytes());
+    let mut slot = (hash as usize) % CAPACITY;
+
+    for _ in 0..CAPACITY {
+        match table.get(slot)? {
+            None => return None,
+            Some(e) if e.package == package && e.hash == hash && (*e.typ).name() == name => return Some(e.typ),
+            Some(_) => slot = (slot + 1) % CAPACITY,
+        }
+    }
+
+    None
+}
+
+// Returns the type already recorded under `typ`'s name in `package`, or
+// records `typ` and returns it. `None` if the table is full.
+unsafe fn find_or_insert(
+    table: &mut [Option<Entry>; CAPACITY],
+    package: *const UPackage,
+    typ: *const UObject,
+) -> Option<*const UObject> {
+    let name = (*typ).name();
+    let hash = fnv1a(name.as_bytes());
+    let mut slot = (hash as usize) % CAPACITY;
+
+    for _ in 0..CAPACITY {
+        let entry = table.get_mut(slot)?;
+
+        match *entry {
+            None => {
+                *entry = Some(Entry { package, hash, typ });
+                return Some(typ);
+            }
+
+            Some(e) if e.package == package && e.hash == hash && (*e.typ).name() == name => return Some(e.typ),
+            Some(_) => slot = (slot + 1) % CAPACITY,
+        }
+    }
+
+    None
+}
@@ -0,0 +1,128 @@
+use super::parameters::{Parameters, TypedefFields};
+use super::Error;
+use common::{List, UFunction, UPackage};
+use core::fmt::Write;
+use core::ptr;
+
+// Parameter layouts we've already emitted a shared struct for, keyed by the
+// module the struct lives in and a hash of its fields. Open addressing keeps
+// lookups cheap across tens of thousands of functions without a heap.
+const CAPACITY: usize = 1 << 17;
+
+// Longest rendered field list we'll try to share. Longer ones get their own
+// struct.
+const MAX_FIELDS_LEN: usize = 8192;
+
+#[derive(Copy, Clone)]
+struct Entry {
+    module: *const UPackage,
+    hash: u64,
+    function: *const UFunction,
+    package: *const UPackage,
+    is_blueprint_generated: bool,
+}
+
+static mut ENTRIES: [Option<Entry>; CAPACITY] = [None; CAPACITY];
+
+// Forgets every layout, so that a new `Generator` emits its shared structs
+// again.
+pub unsafe fn reset() {
+    (*ptr::addr_of_mut!(ENTRIES)).fill(None);
+}
+
+pub enum Interned {
+    // First function with this layout. The caller has to emit the struct.
+    New(u64),
+    Existing(u64),
+}
+
+// Finds or reserves the name of the shared struct for `parameters`. Returns
+// `None` if the layout is too long to compare. Layouts are compared by their
+// rendered fields, so two functions only share a struct if their field names
+// and types match exactly.
+pub unsafe fn intern(
+    function: *const UFunction,
+    package: *const UPackage,
+    is_blueprint_generated: bool,
+    parameters: &Parameters,
+) -> Result<Option<Interned>, Error> {
+    // Every Blueprint-generated struct goes into the same module.
+    let module = if is_blueprint_generated {
+        ptr::null()
+    } else {
+        package
+    };
+
+    let mut fields = List::<u8, MAX_FIELDS_LEN>::new();
+
+    if write!(fields, "{}", TypedefFields(parameters)).is_err() {
+        return Ok(None);
+    }
+
+    let mut hash = fnv1a(fields.as_slice());
+
+    // Each distinct layout gets a distinct hash. On a genuine collision with
+    // a different layout, we move on to the next hash value.
+    'hashes: for _ in 0..CAPACITY {
+        let mut slot = (hash as usize) % CAPACITY;
+
+        for _ in 0..CAPACITY {
+            let entry = match (*ptr::addr_of_mut!(ENTRIES)).get_mut(slot) {
+                Some(entry) => entry,
+                None => break,
+            };
+
+            match *entry {
+                None => {
+                    *entry = Some(Entry {
+                        module,
+                        hash,
+                        function,
+                        package,
+                        is_blueprint_generated,
+                    });
+
+                    return Ok(Some(Interned::New(hash)));
+                }
+
+                Some(e) if e.module == module && e.hash == hash => {
+                    if is_same_layout(&e, fields.as_slice())? {
+                        return Ok(Some(Interned::Existing(hash)));
+                    }
+
+                    hash = hash.wrapping_add(1);
+                    continue 'hashes;
+                }
+
+                Some(_) => slot = (slot + 1) % CAPACITY,
+            }
+        }
+
+        // Every slot is taken.
+        break;
+    }
+
+    Err(Error::MaxSharedParameters)
+}
+
+unsafe fn is_same_layout(entry: &Entry, fields: &[u8]) -> Result<bool, Error> {
+    let parameters = Parameters::new(entry.function, entry.package, entry.is_blueprint_generated)?;
+    let mut existing = List::<u8, MAX_FIELDS_LEN>::new();
+
+    if write!(existing, "{}", TypedefFields(&parameters)).is_err() {
+        return Ok(false);
+    }
+
+    Ok(existing.as_slice() == fields)
+}
+
+pub fn fnv1a(bytes: &[u8]) -> u64 {
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+
+    for &byte in bytes {
+        hash ^= u64::from(byte);
+        hash = hash.wrapping_mul(0x0100_0000_01b3);
+    }
+
+    hash
+}
@@ -0,0 +1,191 @@
+use super::Error;
+use crate::game::{FBoolProperty, FProperty, PropertyDisplayable, TPair, UEnum};
+use common::{EClassCastFlags, FName, UPackage, UStruct};
+use core::fmt::{self, Display, Formatter, Write};
+
+// `GeneratorOptions::emit_layout_json`'s `layout.json`. One `types` array
+// with an object per generated struct or enum, in the order they're generated:
+//
+//     {"types": [
+//         {"kind": "struct", "name": "FSDPlayerController", "module": "FSD",
+//          "full_name": "Class /Script/FSD.FSDPlayerController",
+//          "base": "Class /Script/Engine.PlayerController", "size": 2048, "align": 8,
+//          "fields": [{"name": "...", "offset": 1408, "size": 8, "type": "..."}, ...]},
+//         {"kind": "enum", "name": "EFoo", "module": "FSD", "full_name": "...",
+//          "repr": "u8", "variants": [{"name": "EFoo::Bar", "value": 0}, ...]},
+//         ...
+//     ]}
+//
+// `name` and `module` are the Rust type and the module it's in, `full_name`
+// and `base` the engine's names, and `base` is null for a structure without
+// one. Sizes and offsets are in bytes, as the engine reports them, so `size`
+// doesn't include the tail padding Rust may add. Fields are the structure's
+// own properties under their engine names, with `type` as the SDK spells it.
+// A bitfield field has the offset of the byte it's in, a size of 1, and a
+// `"mask"` of its bit in that byte.
+// Variants are listed without the `_MAX` the engine appends.
+
+pub fn begin(mut out: impl Write) -> Result<(), Error> {
+    out.write_str("{\"types\": [\n")?;
+    Ok(())
+}
+
+pub fn end(mut out: impl Write) -> Result<(), Error> {
+    out.write_str("\n]}\n")?;
+    Ok(())
+}
+
+pub unsafe fn write_struct(
+    mut out: impl Write,
+    first: bool,
+    structure: *const UStruct,
+    name: impl Display,
+    module: &str,
+    package: *const UPackage,
+    is_blueprint_generated: bool,
+) -> Result<(), Error> {
+    separate(&mut out, first)?;
+
+    write!(
+        out,
+        "{{\"kind\": \"struct\", \"name\": \"{}\", \"module\": \"{}\", \"full_name\": \"{}\", \"base\": ",
+        Escaped(name),
+        Escaped(module),
+        Escaped(&*structure),
+    )?;
+
+    let base = (*structure).SuperStruct;
+
+    if base.is_null() {
+        out.write_str("null")?;
+    } else {
+        write!(out, "\"{}\"", Escaped(&*base))?;
+    }
+
+    write!(
+        out,
+        ", \"size\": {}, \"align\": {}, \"fields\": [",
+        (*structure).PropertiesSize,
+        (*structure).MinAlignment,
+    )?;
+
+    let mut property = (*structure).ChildProperties.cast::<FProperty>();
+    let mut first_field = true;
+
+    while !property.is_null() {
+        if !first_field {
+            out.write_str(", ")?;
+        }
+
+        let bitfield = if (*property).is(EClassCastFlags::CASTCLASS_FBoolProperty) {
+            Some(&*property.cast::<FBoolProperty>()).filter(|b| b.is_bitfield())
+        } else {
+            None
+        };
+
+        let (offset, size) = match bitfield {
+            Some(bitfield) => ((*property).Offset + i32::from(bitfield.ByteOffset), 1),
+            None => ((*property).Offset, (*property).ElementSize * (*property).ArrayDim),
+        };
+
+        write!(
+            out,
+            "{{\"name\": \"{}\", \"offset\": {}, \"size\": {}, \"type\": \"{}\"",
+            Escaped((*property).base.NamePrivate),
+            offset,
+            size,
+            Escaped(PropertyDisplayable::new(property, package, is_blueprint_generated)),
+        )?;
+
+        if let Some(bitfield) = bitfield {
+            write!(out, ", \"mask\": {}", bitfield.ByteMask)?;
+        }
+
+        out.write_char('}')?;
+        first_field = false;
+        property = (*property).base.Next.cast();
+    }
+
+    out.write_str("]}")?;
+    Ok(())
+}
+
+pub unsafe fn write_enum(
+    mut out: impl Write,
+    first: bool,
+    enumeration: *const UEnum,
+    module: &str,
+    representation: &str,
+    variants: &[TPair<FName, i64>],
+) -> Result<(), Error> {
+    separate(&mut out, first)?;
+
+    write!(
+        out,
+        "{{\"kind\": \"enum\", \"name\": \"{}\", \"module\": \"{}\", \"full_name\": \"{}\", \"repr\": \"{}\", \"variants\": [",
+        Escaped((*enumeration).name()),
+        Escaped(module),
+        Escaped(&*enumeration),
+        representation,
+    )?;
+
+    for (i, variant) in variants.iter().enumerate() {
+        if i > 0 {
+            out.write_str(", ")?;
+        }
+
+        write!(out, "{{\"name\": \"{}\", \"value\": {}}}", Escaped(variant.Key), variant.Value)?;
+    }
+
+    out.write_str("]}")?;
+    Ok(())
+}
+
+fn separate(mut out: impl Write, first: bool) -> Result<(), Error> {
+    if !first {
+        out.write_str(",\n")?;
+    }
+
+    Ok(())
+}
+
+// Displays a value as the inside of a JSON string.
+struct Escaped<T: Display>(T);
+
+impl<T: Display> Display for Escaped<T> {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(Escaper(f), "{}", self.0)
+    }
+}
+
+struct Escaper<'a, 'b>(&'a mut Formatter<'b>);
+
+impl Write for Escaper<'_, '_> {
+    fn write_str(&mut self, s: &str) -> fmt::Result {
+        for c in s.chars() {
+            match c {
+                '"' => self.0.write_str("\\\"")?,
+                '\\' => self.0.write_str("\\\\")?,
+                c if (c as u32) < 0x20 => write!(self.0, "\\u{:04x}", c as u32)?,
+                c => self.0.write_char(c)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use common::List;
+    use core::str;
+
+    #[test]
+    fn names_are_escaped_inside_json_strings() {
+        let mut out = List::<u8, 64>::new();
+        write!(out, "\"{}\"", Escaped("Say \"Rock\\Stone\"\n\t")).unwrap();
+
+        assert_eq!(str::from_utf8(out.as_slice()).unwrap(), r#""Say \"Rock\\Stone\"\u000a\u0009""#);
+    }
+}
@@ -0,0 +1,238 @@
+// Just enough of the game's name pool and objects for tests, since the real
+// ones only exist inside the game.
+extern crate std;
+
+use crate::game::FProperty;
+use common::{EClassCastFlags, FField, FFieldClass, FFrame, FName, UClass, UFunction, UObject, UPackage, UStruct};
+use core::ffi::c_void;
+use core::mem::{self, MaybeUninit};
+use core::ptr;
+use std::boxed::Box;
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+const BLOCK_LEN: usize = 64 * 1024;
+
+// `common::FNamePool` with a single block.
+#[repr(C)]
+struct NamePool {
+    lock: *mut c_void,
+    current_block: u32,
+    current_byte_cursor: u32,
+    blocks: [*const u8; 1 << 13],
+}
+
+#[repr(C, align(2))]
+struct Block([u8; BLOCK_LEN]);
+
+static mut POOL: NamePool = NamePool {
+    lock: ptr::null_mut(),
+    current_block: 0,
+    current_byte_cursor: 0,
+    blocks: [ptr::null(); 1 << 13],
+};
+
+static mut BLOCK: Block = Block([0; BLOCK_LEN]);
+
+// The byte offset of the next entry in `BLOCK`.
+static CURSOR: Mutex<usize> = Mutex::new(0);
+
+static GENERATING: Mutex<()> = Mutex::new(());
+
+// Generating fills the `static mut` tables in `orphans`, `intern`, `names`,
+// and the other passes, so tests hold this while they generate.
+pub fn generating() -> MutexGuard<'static, ()> {
+    // A test that failed while holding it leaves the tables as usable as before.
+    GENERATING.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+// Adds `text` to our name pool, which becomes `common::NamePoolData`.
+pub fn name(text: &str) -> FName {
+    // An entry is a `FNameEntryHeader`, the length above 6 flag bits, followed
+    // by the text, aligned to 2 bytes. Leave room for reading a whole
+    // `FNameEntry` at the last one.
+    let mut cursor = CURSOR.lock().unwrap();
+    let offset = *cursor;
+    assert!(text.len() < 1024 && offset + 2 + 1024 <= BLOCK_LEN, "mock name pool is full");
+
+    unsafe {
+        let block = ptr::addr_of_mut!(BLOCK).cast::<u8>();
+        let header = (text.len() as u16) << 6;
+        ptr::copy_nonoverlapping(header.to_le_bytes().as_ptr(), block.add(offset), 2);
+        ptr::copy_nonoverlapping(text.as_ptr(), block.add(offset + 2), text.len());
+
+        (*ptr::addr_of_mut!(POOL)).blocks[0] = block;
+        common::NamePoolData = ptr::addr_of!(POOL).cast();
+    }
+
+    *cursor = (offset + 2 + text.len() + 1) & !1;
+
+    // `FName { ComparisonIndex, Number }`, with the index in 2-byte strides
+    // into block 0.
+    unsafe { mem::transmute::<[u32; 2], FName>([(offset / 2) as u32, 0]) }
+}
+
+// `common::UObject`, with its private fields.
+#[repr(C)]
+struct Object {
+    vtable: *mut *const c_void,
+    flags: u32,
+    index: i32,
+    class: *const UObject,
+    name: FName,
+    outer: *mut UObject,
+}
+
+const _: () = assert!(mem::size_of::<Object>() == mem::size_of::<UObject>());
+
+// A leaked object named `name`. Only its name, class, and outer are set.
+pub fn object(name: &str, class: *const UObject, outer: *mut UObject) -> *mut UObject {
+    let object = Box::new(Object {
+        vtable: ptr::null_mut(),
+        flags: 0,
+        index: 0,
+        class,
+        name: self::name(name),
+        outer,
+    });
+
+    Box::leak(object) as *mut Object as *mut UObject
+}
+
+// A leaked, zeroed `T` that starts with a `UObject` of that name, class, and
+// outer.
+fn leak_object<T>(name: &str, class: *const UClass, outer: *mut UObject) -> *mut T {
+    assert!(mem::size_of::<T>() >= mem::size_of::<Object>());
+    let object = Box::leak(Box::new(MaybeUninit::<T>::zeroed())).as_mut_ptr();
+
+    unsafe {
+        object.cast::<Object>().write(Object {
+            vtable: ptr::null_mut(),
+            flags: 0,
+            index: 0,
+            class: class.cast(),
+            name: self::name(name),
+            outer,
+        });
+    }
+
+    object
+}
+
+// A leaked class named `name`, whose instances are `flags`. Its own class is
+// the metaclass `Class`.
+pub fn class(name: &str, flags: EClassCastFlags, outer: *mut UObject) -> *mut UClass {
+    static METACLASS: Mutex<usize> = Mutex::new(0);
+    let mut metaclass = METACLASS.lock().unwrap();
+
+    if *metaclass == 0 {
+        let class = leak_object::<UClass>("Class", ptr::null(), ptr::null_mut());
+
+        unsafe {
+            (*class).ClassCastFlags = EClassCastFlags::CASTCLASS_UClass;
+            (*class.cast::<Object>()).class = class.cast();
+        }
+
+        *metaclass = class as usize;
+    }
+
+    let class = leak_object::<UClass>(name, *metaclass as *const UClass, outer);
+    unsafe { (*class).ClassCastFlags = flags };
+    class
+}
+
+// A leaked package with the full name `name`, e.g. "/Script/FSD".
+pub fn package(name: &str) -> *mut UPackage {
+    let class = class("Package", EClassCastFlags::CASTCLASS_UPackage, ptr::null_mut());
+    leak_object(name, class, ptr::null_mut())
+}
+
+// A leaked script struct of `size` bytes, aligned to `align`, in `package`,
+// with `properties` in that order.
+pub fn script_struct(
+    name: &str,
+    package: *mut UPackage,
+    size: i32,
+    align: i32,
+    properties: &[*mut FProperty],
+) -> *mut UStruct {
+    let flags = EClassCastFlags::CASTCLASS_UField | EClassCastFlags::CASTCLASS_UStruct | EClassCastFlags::CASTCLASS_UScriptStruct;
+    let structure = structure::<UStruct>(name, class("ScriptStruct", flags, ptr::null_mut()), package.cast(), properties);
+
+    unsafe {
+        (*structure).PropertiesSize = size;
+        (*structure).MinAlignment = align;
+    }
+
+    structure
+}
+
+// A leaked function of `outer` with the parameters `properties`.
+pub fn function(name: &str, outer: *mut UObject, properties: &[*mut FProperty]) -> *mut UFunction {
+    let flags = EClassCastFlags::CASTCLASS_UField | EClassCastFlags::CASTCLASS_UStruct | EClassCastFlags::CASTCLASS_UFunction;
+    let function = structure::<UFunction>(name, class("Function", flags, ptr::null_mut()), outer, properties);
+
+    // Zero isn't a valid function pointer.
+    unsafe extern "C" fn native(_: *mut UObject, _: *mut FFrame, _: *mut c_void) {}
+    unsafe { ptr::addr_of_mut!((*function).Func).write(native) };
+
+    function
+}
+
+// A leaked `T`, a `UStruct` or derived from one, with `properties` linked in
+// order.
+fn structure<T>(name: &str, class: *const UClass, outer: *mut UObject, properties: &[*mut FProperty]) -> *mut T {
+    let structure = leak_object::<T>(name, class, outer);
+    let base = structure.cast::<UStruct>();
+
+    unsafe {
+        (*base).ChildProperties = properties.first().map_or(ptr::null(), |&p| p.cast::<FField>());
+
+        for pair in properties.windows(2) {
+            (*pair[0]).base.Next = pair[1].cast();
+        }
+    }
+
+    structure
+}
+
+// A leaked property of type `id`, `size` bytes at `offset`. It has room for
+// the fields of any property subclass, see `set_subclass_field()`.
+pub fn property(name: &str, id: EClassCastFlags, offset: i32, size: i32) -> *mut FProperty {
+    let class = Box::leak(Box::new(unsafe { mem::zeroed::<FFieldClass>() }));
+    class.Id = id;
+    class.CastFlags = id | EClassCastFlags::CASTCLASS_FProperty;
+
+    let property = Box::leak(Box::new([0u64; 32])) as *mut [u64; 32] as *mut FProperty;
+
+    unsafe {
+        (*property).base.ClassPrivate = class;
+        (*property).base.NamePrivate = self::name(name);
+        (*property).ArrayDim = 1;
+        (*property).ElementSize = size;
+        (*property).Offset = offset;
+    }
+
+    property
+}
+
+// Sets the first field of `property`'s subclass, right after its `FProperty`,
+// e.g. `FArrayProperty::Inner` or `FStructProperty::Structure`.
+pub fn set_subclass_field<T>(property: *mut FProperty, value: *const T) {
+    unsafe { property.add(1).cast::<*const T>().write(value) };
+}
+
+// A leaked one-byte bitfield bool at `offset`, the bit of `mask`.
+pub fn bitfield(name: &str, offset: i32, mask: u8) -> *mut FProperty {
+    wide_bitfield(name, offset, 1, 0, mask)
+}
+
+// A leaked bitfield bool at `offset` whose storage is `size` bytes long, the
+// bit of `mask` in byte `byte` of it.
+pub fn wide_bitfield(name: &str, offset: i32, size: u8, byte: u8, mask: u8) -> *mut FProperty {
+    let property = property(name, EClassCastFlags::CASTCLASS_FBoolProperty, offset, size.into());
+
+    // `FBoolProperty::{FieldSize, ByteOffset, ByteMask, FieldMask}`.
+    unsafe { property.add(1).cast::<[u8; 4]>().write([size, byte, mask, mask]) };
+
+    property
+}
@@ -0,0 +1,76 @@
+use super::intern::fnv1a;
+use super::CleanedName;
+use common::{FName, List, UPackage};
+use core::fmt::Write;
+use core::ptr;
+
+// The names that `CleanedName` changed, for `GeneratorOptions::emit_name_maps`.
+// Keyed by the module they went into, where null is `blueprint_generated`, and
+// the original name, so that a name used in many places is only recorded once
+// per module. Open addressing like in `intern`. Once the table is full, further
+// renames are left out of the maps.
+const CAPACITY: usize = 1 << 16;
+
+#[derive(Copy, Clone)]
+struct Entry {
+    package: *const UPackage,
+    name: FName,
+}
+
+static mut RENAMES: [Option<Entry>; CAPACITY] = [None; CAPACITY];
+
+// Forgets every rename, for a new `Generator`.
+pub unsafe fn reset() {
+    (*ptr::addr_of_mut!(RENAMES)).fill(None);
+}
+
+// Records `name` if cleaning it changes it.
+pub unsafe fn record(package: *const UPackage, name: FName) {
+    let cleaned = CleanedName::new(name);
+    let mut text = List::<u8, 1024>::new();
+
+    if write!(text, "{}", cleaned).is_err() || is_unchanged(name, text.as_slice()) {
+        return;
+    }
+
+    let table = &mut *ptr::addr_of_mut!(RENAMES);
+    let original = name.text();
+    let hash = fnv1a(original.as_bytes()) ^ u64::from(name.number());
+    let mut slot = (hash as usize) % CAPACITY;
+
+    for _ in 0..CAPACITY {
+        let entry = match table.get_mut(slot) {
+            Some(entry) => entry,
+            None => return,
+        };
+
+        match *entry {
+            None => {
+                *entry = Some(Entry { package, name });
+                return;
+            }
+
+            Some(e) if e.package == package && is_same(e.name, name) => return,
+            Some(_) => slot = (slot + 1) % CAPACITY,
+        }
+    }
+}
+
+// Calls `f` with the original of each name recorded for `package`.
+pub unsafe fn for_each(package: *const UPackage, mut f: impl FnMut(FName)) {
+    for entry in (*ptr::addr_of!(RENAMES)).iter().flatten() {
+        if entry.package == package {
+            f(entry.name);
+        }
+    }
+}
+
+unsafe fn is_same(a: FName, b: FName) -> bool {
+    a.number() == b.number() && a.text() == b.text()
+}
+
+// Whether `cleaned` spells the name the way `FName`'s `Display` does.
+unsafe fn is_unchanged(name: FName, cleaned: &[u8]) -> bool {
+    let mut original = List::<u8, 1024>::new();
+    write!(original, "{}", name).is_ok() && original.as_slice() == cleaned
+}
@@ -0,0 +1,343 @@
+// Buffer size for `blueprint_generated.rs`, by far the largest file in the
+// SDK. Each time it fills up costs one `WriteFile`.
+pub const BLUEPRINT_GENERATED_BUFFER_SIZE: usize = 256 * 1024;
+
+// A starting point for `GeneratorOptions::common_prelude`.
+#[allow(dead_code)]
+pub const COMMON_PRELUDE: &[&str] = &[
+    "UObject",
+    "UClass",
+    "UStruct",
+    "UFunction",
+    "FName",
+    "FString",
+    "FText",
+    "TArray",
+    "TMap",
+    "ObjPtr",
+    "ClassPtr",
+    "TSubclassOf",
+    "TWeakObjectPtr",
+];
+
+#[derive(Copy, Clone)]
+pub struct GeneratorOptions {
+    // Emit a `#[repr(C)] pub struct {Struct}_{Function}_Params` for every
+    // function, for detours of `UFunction::Func`. `(*Stack).Locals` only has
+    // this layout when the function was called through ProcessEvent.
+    pub emit_function_typedefs: bool,
+
+    // With `emit_function_typedefs`, share one `Params_{hash}` struct between
+    // the functions of a module with the same parameter layout.
+    pub share_function_typedefs: bool,
+
+    // With `emit_function_typedefs`, implement `Debug` for the `_Params`
+    // structs. Out parameters print as `"<out>"`.
+    pub emit_params_debug: bool,
+
+    // Start every package module with a `//!` comment counting its structs,
+    // enums, and functions.
+    pub emit_package_docs: bool,
+
+    // Emit `spawn(world, transform, owner)` for every actor class. See
+    // `common::spawn_actor()`.
+    pub emit_spawn_helpers: bool,
+
+    // List each struct's outer chain in its header comment.
+    pub emit_outer_chains: bool,
+
+    // Give the root class `owner()` and `owner_as::<T>()`, which walk the
+    // outer chain.
+    pub emit_owner_accessors: bool,
+
+    // Assert the element sizes of `TArray` and `TMap` fields.
+    pub emit_container_asserts: bool,
+
+    // Assert every field's offset and every struct's size in `#[cfg(test)]`
+    // consts. Bitfields and padding aren't checked.
+    pub emit_offset_asserts: bool,
+
+    // Assert that each function wrapper's `Parameters` has the engine's size.
+    pub emit_params_asserts: bool,
+
+    // Type object and class fields as `common::ObjPtr<T>` and
+    // `common::ClassPtr<T>` rather than `*mut T`.
+    pub wrap_pointers: bool,
+
+    // Import field types from other packages by their short name, unless the
+    // name would clash.
+    pub emit_package_imports: bool,
+
+    // Write structs as `common::define_struct!` invocations. The expanded types
+    // are the same.
+    pub compact_structs: bool,
+
+    // Put `#[deprecated]` on fields of deprecated properties.
+    pub emit_deprecated_markers: bool,
+
+    // Put `CPF_EditorOnly` fields behind `#[cfg(feature = "editor")]`. With
+    // `emit_offset_asserts`, both layouts are asserted.
+    pub gate_editor_only_fields: bool,
+
+    // Log the structs, classes, and enums that nothing generated refers to.
+    pub report_orphans: bool,
+
+    // Emit `From` conversions between structs with identical layouts. See
+    // `conversions::find_identical()`.
+    pub emit_conversions: bool,
+
+    // Emit `functions::FUNCTIONS`, every function's full name and owner. This
+    // is a few MB of strings.
+    pub emit_function_index: bool,
+
+    // Emit `classes::CLASSES` and `classes::find(name)`, which look up a
+    // generated class's `UClass` by its Rust name.
+    pub emit_class_table: bool,
+
+    // Write `layout.json` describing every struct and enum. The schema is at
+    // the top of `generator/json.rs`.
+    pub emit_layout_json: bool,
+
+    // Implement `Deref<Target = {repr}>` for every enum.
+    pub emit_enum_deref: bool,
+
+    // Give every enum `try_from_{repr}(v)`, which is `None` unless `v` is one
+    // of its constants.
+    pub emit_enum_try_from: bool,
+
+    // Emit single-variant enums without the optional impls.
+    pub trim_single_variant_enums: bool,
+
+    // Emit `Foo_at(i)` and `Foo_at_mut(i)` for every array field `Foo`.
+    pub emit_array_accessors: bool,
+
+    // Emit `iter_Foo()` over the non-null pointers of every `TArray` field
+    // `Foo` of object or class pointers.
+    pub emit_pointer_iterators: bool,
+
+    // Emit a `Foo_FLAGS` constant with the `PropertyFlags` of every field
+    // `Foo`.
+    pub emit_property_flags: bool,
+
+    // Give every struct a `LAYOUT` string of its own fields, e.g.
+    // "0x28:0x8:Owner;0x30:0x1:bHidden".
+    pub emit_layout_strings: bool,
+
+    // Emit `to_bytes()` and `from_bytes()` for plain-old-data structs.
+    pub emit_pod_bytes: bool,
+
+    // Implement `common::Reflected` for every struct, class, and enum.
+    pub emit_reflected: bool,
+
+    // Emit `validate::validate()`, which logs the types whose size or
+    // alignment no longer matches the game. Implies `emit_reflected`.
+    pub emit_validator: bool,
+
+    // Write each package as `{package}/mod.rs` with separate struct and enum
+    // files.
+    pub split_packages: bool,
+
+    // Write package files under `src/packages/`.
+    pub packages_subdirectory: bool,
+
+    // Write `sdk_info.rs`, which sums up the dump and the options used.
+    pub emit_sdk_info: bool,
+
+    // End each module with a `NAME_MAP` from renamed identifiers to the
+    // engine's names.
+    pub emit_name_maps: bool,
+
+    // Stop with `Error::TooManyWarnings` after this many warnings. `None`
+    // never stops.
+    pub max_warnings: Option<u32>,
+
+    // Generated types to re-export from `prelude` in `lib.rs`.
+    pub prelude: &'static [&'static str],
+
+    // Names from `common` to re-export from `prelude` too, e.g.
+    // `COMMON_PRELUDE`.
+    pub common_prelude: &'static [&'static str],
+
+    // Short names of objects, or of the classes of objects, never to generate.
+    pub skip_classes: &'static [&'static str],
+
+    // Full names of functions whose wrappers get a note on calling them with
+    // `common::call_native!`.
+    pub native_call_notes: &'static [&'static str],
+
+    // Short names of packages to declare in `lib.rs` without regenerating.
+    pub unchanged_packages: &'static [&'static str],
+
+    // Inner attributes at the top of `lib.rs`, one `#![...]` per line.
+    pub lib_rs_attributes: &'static [&'static str],
+
+    // Names of the `common` and `macros` crates as the SDK's dependencies.
+    pub common_crate: &'static str,
+    pub macros_crate: &'static str,
+
+    pub edition: Edition,
+
+    // Write a `Cargo.toml` next to `src/lib.rs`. `None` keeps an existing one.
+    pub cargo_toml: Option<CargoToml>,
+
+    // Put function wrappers behind Cargo features. See `FunctionFeatures`.
+    pub function_features: FunctionFeatures,
+}
+
+// Only the `impl` blocks of function wrappers are gated, so turning features
+// off can't change a layout.
+// Variants are only chosen in `GENERATOR_OPTIONS`, hence the allow.
+#[allow(dead_code)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum FunctionFeatures {
+    // Every function wrapper is always compiled.
+    Off,
+
+    // `fn_<package>`, e.g. `fn_FSD`.
+    PerPackage,
+
+    // `fn_<package>_<struct>`, e.g. `fn_Engine_GameplayStatics`.
+    PerStruct,
+}
+
+// The Rust edition and compiler the generated SDK has to build with.
+// Variants are only chosen in `GENERATOR_OPTIONS`, hence the allow.
+#[allow(dead_code)]
+#[derive(Copy, Clone, PartialEq, Eq)]
+pub enum Edition {
+    // Edition 2021, Rust 1.77 or newer. Uses `core::mem::offset_of!`.
+    Rust2021,
+
+    // Edition 2018, Rust 1.65 or newer. Uses `common::offset_of!`.
+    Rust2018,
+}
+
+impl Edition {
+    pub const fn name(self) -> &'static str {
+        match self {
+            Self::Rust2021 => "2021",
+            Self::Rust2018 => "2018",
+        }
+    }
+
+    pub const fn offset_of(self) -> &'static str {
+        match self {
+            Self::Rust2021 => "core::mem::offset_of!",
+            Self::Rust2018 => "common::offset_of!",
+        }
+    }
+}
+
+#[derive(Copy, Clone)]
+pub struct CargoToml {
+    pub crate_name: &'static str,
+
+    // Relative to the SDK directory, with forward slashes.
+    pub common_path: &'static str,
+    pub macros_path: &'static str,
+}
+
+impl GeneratorOptions {
+    pub const DEFAULT: Self = Self {
+        emit_function_typedefs: false,
+        share_function_typedefs: false,
+        emit_params_debug: false,
+        emit_package_docs: false,
+        emit_spawn_helpers: false,
+        emit_outer_chains: false,
+        emit_owner_accessors: false,
+        emit_container_asserts: false,
+        emit_offset_asserts: false,
+        emit_params_asserts: false,
+        wrap_pointers: false,
+        emit_package_imports: false,
+        compact_structs: false,
+        emit_deprecated_markers: false,
+        gate_editor_only_fields: false,
+        report_orphans: false,
+        emit_conversions: false,
+        emit_function_index: false,
+        emit_class_table: false,
+        emit_layout_json: false,
+        emit_enum_deref: false,
+        emit_enum_try_from: false,
+        trim_single_variant_enums: false,
+        emit_array_accessors: false,
+        emit_pointer_iterators: false,
+        emit_property_flags: false,
+        emit_layout_strings: false,
+        emit_pod_bytes: false,
+        emit_reflected: false,
+        emit_validator: false,
+        split_packages: false,
+        packages_subdirectory: false,
+        emit_sdk_info: false,
+        emit_name_maps: false,
+        max_warnings: None,
+        prelude: &[],
+        common_prelude: &[],
+        skip_classes: &[],
+        native_call_notes: &[],
+        unchanged_packages: &[],
+        lib_rs_attributes: &[
+            "#![no_std]",
+            "#![allow(dead_code, non_camel_case_types, non_snake_case)]",
+            "#![allow(clippy::missing_safety_doc, clippy::too_many_arguments, clippy::type_complexity)]",
+        ],
+        common_crate: "common",
+        macros_crate: "macros",
+        edition: Edition::Rust2021,
+        cargo_toml: None,
+        function_features: FunctionFeatures::Off,
+    };
+}
+
+impl GeneratorOptions {
+    // Every on/off option and its value, for `sdk_info.rs`.
+    pub fn flags(&self) -> [(&'static str, bool); 36] {
+        [
+            ("emit_function_typedefs", self.emit_function_typedefs),
+            ("share_function_typedefs", self.share_function_typedefs),
+            ("emit_params_debug", self.emit_params_debug),
+            ("emit_package_docs", self.emit_package_docs),
+            ("emit_spawn_helpers", self.emit_spawn_helpers),
+            ("emit_outer_chains", self.emit_outer_chains),
+            ("emit_owner_accessors", self.emit_owner_accessors),
+            ("emit_container_asserts", self.emit_container_asserts),
+            ("emit_offset_asserts", self.emit_offset_asserts),
+            ("emit_params_asserts", self.emit_params_asserts),
+            ("wrap_pointers", self.wrap_pointers),
+            ("emit_package_imports", self.emit_package_imports),
+            ("compact_structs", self.compact_structs),
+            ("emit_deprecated_markers", self.emit_deprecated_markers),
+            ("gate_editor_only_fields", self.gate_editor_only_fields),
+            ("report_orphans", self.report_orphans),
+            ("emit_conversions", self.emit_conversions),
+            ("emit_function_index", self.emit_function_index),
+            ("emit_class_table", self.emit_class_table),
+            ("emit_layout_json", self.emit_layout_json),
+            ("emit_enum_deref", self.emit_enum_deref),
+            ("emit_enum_try_from", self.emit_enum_try_from),
+            ("trim_single_variant_enums", self.trim_single_variant_enums),
+            ("emit_array_accessors", self.emit_array_accessors),
+            ("emit_pointer_iterators", self.emit_pointer_iterators),
+            ("emit_property_flags", self.emit_property_flags),
+            ("emit_layout_strings", self.emit_layout_strings),
+            ("emit_pod_bytes", self.emit_pod_bytes),
+            ("emit_reflected", self.emit_reflected),
+            ("emit_validator", self.emit_validator),
+            ("split_packages", self.split_packages),
+            ("packages_subdirectory", self.packages_subdirectory),
+            ("emit_sdk_info", self.emit_sdk_info),
+            ("emit_name_maps", self.emit_name_maps),
+            ("cargo_toml", self.cargo_toml.is_some()),
+            ("function_features", self.function_features != FunctionFeatures::Off),
+        ]
+    }
+}
+
+impl Default for GeneratorOptions {
+    fn default() -> Self {
+        Self::DEFAULT
+    }
+}
@@ -0,0 +1,150 @@
+use crate::game::FProperty;
+use common::UObject;
+
+// Bit sets indexed by `UObject::InternalIndex`: the types we generated, and the
+// types that some generated field, function parameter, or base class names.
+// Objects past `MAX_OBJECTS` are ignored. Besides the orphan report, the
+// generator uses these to find referenced types that were never generated.
+const MAX_OBJECTS: usize = 1 << 22;
+
+static mut GENERATED: [u64; MAX_OBJECTS / 64] = [0; MAX_OBJECTS / 64];
+static mut REFERENCED: [u64; MAX_OBJECTS / 64] = [0; MAX_OBJECTS / 64];
+
+// Clears both sets, for a new `Generator`.
+pub unsafe fn reset() {
+    (*core::ptr::addr_of_mut!(GENERATED)).fill(0);
+    (*core::ptr::addr_of_mut!(REFERENCED)).fill(0);
+}
+
+unsafe fn set(bits: &mut [u64; MAX_OBJECTS / 64], object: *const UObject) {
+    if object.is_null() {
+        return;
+    }
+
+    let index = (*object).InternalIndex as usize;
+
+    if index < MAX_OBJECTS {
+        bits[index / 64] |= 1 << (index % 64);
+    }
+}
+
+pub unsafe fn mark_generated(object: *const UObject) {
+    set(&mut *core::ptr::addr_of_mut!(GENERATED), object);
+}
+
+pub unsafe fn mark_referenced(object: *const UObject) {
+    set(&mut *core::ptr::addr_of_mut!(REFERENCED), object);
+}
+
+pub unsafe fn mark_property(property: *const FProperty) {
+    (*property).for_each_referenced_type(&mut |object| mark_referenced(object));
+}
+
+// The first object at `index` or after that something generated refers to
+// but that wasn't generated itself, and its index. Reads the bit sets afresh
+// on every call, so callers may generate more types in between.
+pub unsafe fn next_ungenerated(mut index: usize) -> Option<(usize, *mut UObject)> {
+    let generated = &*core::ptr::addr_of!(GENERATED);
+    let referenced = &*core::ptr::addr_of!(REFERENCED);
+
+    while let Some(i) = next_ungenerated_index(generated, referenced, index) {
+        let item = (*common::GUObjectArray).index_to_object(i as i32);
+
+        if !item.is_null() && !(*item).Object.is_null() {
+            return Some((i, (*item).Object));
+        }
+
+        index = i + 1;
+    }
+
+    None
+}
+
+// The first index at `index` or after that is in `referenced` but not in
+// `generated`.
+fn next_ungenerated_index(generated: &[u64], referenced: &[u64], index: usize) -> Option<usize> {
+    let mut word = index / 64;
+    let mut mask = u64::MAX << (index % 64);
+
+    while let (Some(&generated), Some(&referenced)) = (generated.get(word), referenced.get(word)) {
+        let ungenerated = referenced & !generated & mask;
+
+        if ungenerated != 0 {
+            return Some(word * 64 + ungenerated.trailing_zeros() as usize);
+        }
+
+        word += 1;
+        mask = u64::MAX;
+    }
+
+    None
+}
+
+// Logs every generated type that nothing generated refers to, and returns how
+// many there were.
+pub unsafe fn report() -> u32 {
+    let generated = &*core::ptr::addr_of!(GENERATED);
+    let referenced = &*core::ptr::addr_of!(REFERENCED);
+    let mut orphans = 0;
+
+    for (word, (&generated, &referenced)) in generated.iter().zip(referenced.iter()).enumerate() {
+        let mut unreferenced = generated & !referenced;
+
+        while unreferenced != 0 {
+            let bit = unreferenced.trailing_zeros() as usize;
+            unreferenced &= unreferenced - 1;
+
+            let item = (*common::GUObjectArray).index_to_object((word * 64 + bit) as i32);
+
+            if !item.is_null() && !(*item).Object.is_null() {
+                common::log!("orphan: {}", *(*item).Object);
+                orphans += 1;
+            }
+        }
+    }
+
+    orphans
+}
+
+#[cfg(test)]
+mod tests {
+    extern crate std;
+
+    use super::*;
+    use crate::generator::mock;
+    use common::EClassCastFlags;
+    use core::ptr;
+    use std::boxed::Box;
+    use std::vec;
+
+    fn bits() -> Box<[u64; MAX_OBJECTS / 64]> {
+        vec![0; MAX_OBJECTS / 64].into_boxed_slice().try_into().unwrap()
+    }
+
+    #[test]
+    fn an_enum_of_a_package_that_was_left_out_is_found() {
+        let class = mock::class("Enum", EClassCastFlags::CASTCLASS_UEnum, ptr::null_mut());
+        let grenade = mock::object("EGrenadeType", class.cast(), mock::package("/Script/Filtered").cast());
+        unsafe { (*grenade).InternalIndex = 70 };
+
+        let field = mock::property("GrenadeType", EClassCastFlags::CASTCLASS_FByteProperty, 0, 1);
+        mock::set_subclass_field(field, grenade);
+        let loadout = mock::script_struct("Loadout", mock::package("/Script/FSD"), 1, 1, &[field]);
+        unsafe { (*loadout.cast::<UObject>()).InternalIndex = 3 };
+
+        // What generating `Loadout` marks, see `mark_generated()` and
+        // `mark_property()`.
+        let (mut generated, mut referenced) = (bits(), bits());
+
+        unsafe {
+            set(&mut generated, loadout.cast());
+            (*field).for_each_referenced_type(&mut |object| set(&mut referenced, object));
+        }
+
+        assert_eq!(next_ungenerated_index(&*generated, &*referenced, 0), Some(70));
+        assert_eq!(next_ungenerated_index(&*generated, &*referenced, 71), None);
+
+        unsafe { set(&mut generated, grenade) };
+        assert_eq!(next_ungenerated_index(&*generated, &*referenced, 0), None);
+    }
+}
@@ -0,0 +1,313 @@
+use super::{CleanedName, Error, Identifier};
+use crate::game::{FProperty, PropertyDisplayable};
+
+use common::{EPropertyFlags, List, UFunction, UPackage};
+
+use core::fmt::{self, Display, Formatter};
+
+pub enum Kind {
+    Input,
+    Output,
+}
+
+pub struct Parameter {
+    pub property: *const FProperty,
+    pub kind: Kind,
+}
+
+pub struct Parameters {
+    pub parameters: List<Parameter, 32>,
+    package: *const UPackage,
+    is_struct_blueprint_generated: bool,
+    num_outputs: u8,
+}
+
+impl Parameters {
+    pub unsafe fn new(
+        function: *const UFunction,
+        package: *const UPackage,
+        is_struct_blueprint_generated: bool,
+    ) -> Result<Parameters, Error> {
+        let mut parameters = Parameters {
+            parameters: List::new(),
+            package,
+            is_struct_blueprint_generated,
+            num_outputs: 0,
+        };
+
+        let mut property = (&*function).ChildProperties.cast::<FProperty>();
+
+        while !property.is_null() {
+            parameters.process(property)?;
+            property = (*property).base.Next.cast::<FProperty>();
+        }
+
+        Ok(parameters)
+    }
+
+    fn add(&mut self, parameter: Parameter) -> Result<(), Error> {
+        self.parameters
+            .push(parameter)
+            .map_err(|_| Error::MaxParameters)?;
+        Ok(())
+    }
+
+    fn process(&mut self, property: *const FProperty) -> Result<(), Error> {
+        let flags = unsafe { (*property).PropertyFlags };
+
+        let kind = if flags.contains(EPropertyFlags::CPF_ReturnParm) || (flags.contains(EPropertyFlags::CPF_OutParm) && !flags.contains(EPropertyFlags::CPF_ConstParm)) {
+            self.num_outputs += 1;
+            Kind::Output
+        } else if flags.contains(EPropertyFlags::CPF_Parm) {
+            Kind::Input
+        } else {
+            return Ok(());
+        };
+
+        self.add(Parameter { property, kind })?;
+
+        Ok(())
+    }
+
+    // Output parameters in declaration order, including the `ReturnParm`
+    // wherever it was declared. Both the signature (`Outputs`) and the returned
+    // tuple (`ReturnValues`) are built from this so their orders always agree.
+    fn outputs(&self) -> impl Iterator<Item = *const FProperty> + '_ {
+        self.parameters
+            .iter()
+            .filter(|p| matches!(p.kind, Kind::Output))
+            .map(|p| p.property)
+    }
+
+    fn typ(&self, property: *const FProperty) -> PropertyDisplayable {
+        PropertyDisplayable::new(property, self.package, self.is_struct_blueprint_generated)
+    }
+}
+
+pub struct Inputs<'a>(pub &'a Parameters);
+
+impl<'a> Display for Inputs<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        for parameter in self.0.parameters.iter() {
+            if let Kind::Input = parameter.kind {
+                let parameter = parameter.property;
+                let name = Identifier(CleanedName::new(unsafe { (*parameter).base.NamePrivate }));
+                write!(f, "{}: {}, ", name, self.0.typ(parameter))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct Outputs<'a>(pub &'a Parameters);
+
+impl<'a> Display for Outputs<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self.0.num_outputs {
+            0 => return Ok(()),
+            1 => write!(f, "-> ")?,
+            _ => write!(f, "-> (")?,
+        }
+
+        for property in self.0.outputs() {
+            let typ = self.0.typ(property);
+
+            if self.0.num_outputs == 1 {
+                write!(f, "{} ", typ)?;
+                return Ok(());
+            } else {
+                write!(f, "{}, ", typ)?;
+            }
+        }
+
+        if self.0.num_outputs > 1 {
+            write!(f, ") ")?;
+        }
+
+        Ok(())
+    }
+}
+
+pub struct DeclareStructFields<'a>(pub &'a Parameters);
+
+impl<'a> Display for DeclareStructFields<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        for parameter in self.0.parameters.iter() {
+            let property = parameter.property;
+            let name = Identifier(CleanedName::new(unsafe { (*property).base.NamePrivate }));
+            let typ = self.0.typ(property);
+
+            if let Kind::Input = parameter.kind {
+                write!(f, "\n            {}: {}, ", name, typ)?;
+            } else {
+                write!(
+                    f,
+                    "\n            {}: core::mem::MaybeUninit<{}>, ",
+                    name, typ
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct InitStructFields<'a>(pub &'a Parameters);
+
+impl<'a> Display for InitStructFields<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        for parameter in self.0.parameters.iter() {
+            let name = Identifier(CleanedName::new(unsafe { (*parameter.property).base.NamePrivate }));
+
+            if let Kind::Input = parameter.kind {
+                write!(f, "\n            {}, ", name)?;
+            } else {
+                write!(
+                    f,
+                    "\n            {}: core::mem::MaybeUninit::uninit(), ",
+                    name
+                )?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+pub struct ReturnValues<'a>(pub &'a Parameters);
+
+impl<'a> Display for ReturnValues<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        match self.0.num_outputs {
+            0 => return Ok(()),
+            1 => write!(f, "\n        ")?,
+            _ => write!(f, "\n        (")?,
+        }
+
+        for property in self.0.outputs() {
+            let name = Identifier(CleanedName::new(unsafe { (*property).base.NamePrivate }));
+
+            if self.0.num_outputs == 1 {
+                write!(f, "parameters.{}.assume_init()", name)?;
+                return Ok(());
+            } else {
+                write!(f, "parameters.{}.assume_init(), ", name)?;
+            }
+        }
+
+        if self.0.num_outputs > 1 {
+            write!(f, ")")?;
+        }
+
+        Ok(())
+    }
+}
+
+// Fields of a standalone parameter struct. Unlike `DeclareStructFields`, every
+// field is public and plainly typed because the struct describes a frame that
+// the engine already initialized. Outputs are marked, which also keeps
+// `intern` from sharing a layout between functions that only differ in which
+// parameters are outputs.
+pub struct TypedefFields<'a>(pub &'a Parameters);
+
+impl<'a> Display for TypedefFields<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        for parameter in self.0.parameters.iter() {
+            let property = parameter.property;
+            let name = Identifier(CleanedName::new(unsafe { (*property).base.NamePrivate }));
+            write!(f, "\n    pub {}: {},", name, self.0.typ(property))?;
+
+            if let Kind::Output = parameter.kind {
+                write!(f, " // out")?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+// The `.field()` calls of a standalone parameter struct's `Debug` impl.
+pub struct DebugFields<'a>(pub &'a Parameters);
+
+impl<'a> Display for DebugFields<'a> {
+    fn fmt(&self, f: &mut Formatter) -> Result<(), fmt::Error> {
+        for parameter in self.0.parameters.iter() {
+            let property = parameter.property;
+            let name = unsafe { (*property).base.NamePrivate };
+            write!(f, "\n            .field(\"{}\", ", CleanedName::new(name))?;
+
+            if let Kind::Output = parameter.kind {
+                write!(f, "&\"<out>\")")?;
+            } else if unsafe { (*property).is_debug() } {
+                write!(f, "&self.{})", Identifier(CleanedName::new(name)))?;
+            } else {
+                write!(f, "&\"<{}>\")", self.0.typ(property))?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generator::mock;
+    use common::EClassCastFlags;
+    use core::ptr;
+    use core::str;
+
+    fn parameter(name: &str, id: EClassCastFlags, flags: &[EPropertyFlags]) -> *mut FProperty {
+        let property = mock::property(name, id, 0, 4);
+        unsafe { (*property).PropertyFlags = EPropertyFlags(flags.iter().fold(0, |all, flag| all | flag.0)) };
+        property
+    }
+
+    #[test]
+    fn outputs_and_return_values_follow_declaration_order() {
+        let function = mock::function(
+            "GetAmmo",
+            ptr::null_mut(),
+            &[
+                parameter("Slot", EClassCastFlags::CASTCLASS_FIntProperty, &[EPropertyFlags::CPF_Parm]),
+                parameter("ReturnValue", EClassCastFlags::CASTCLASS_FIntProperty, &[EPropertyFlags::CPF_Parm, EPropertyFlags::CPF_ReturnParm]),
+                parameter("Heat", EClassCastFlags::CASTCLASS_FFloatProperty, &[EPropertyFlags::CPF_Parm, EPropertyFlags::CPF_OutParm]),
+                parameter("Limit", EClassCastFlags::CASTCLASS_FFloatProperty, &[EPropertyFlags::CPF_Parm, EPropertyFlags::CPF_OutParm, EPropertyFlags::CPF_ConstParm]),
+            ],
+        );
+
+        let parameters = unsafe { Parameters::new(function, ptr::null(), false) }.unwrap();
+        let mut out = List::<u8, 256>::new();
+        fmt::write(&mut out, format_args!("{}|{}|{}", Inputs(&parameters), Outputs(&parameters), ReturnValues(&parameters))).unwrap();
+
+        assert_eq!(
+            str::from_utf8(out.as_slice()).unwrap(),
+            "Slot: i32, Limit: f32, |-> (i32, f32, ) |\n        (parameters.ReturnValue.assume_init(), parameters.Heat.assume_init(), )",
+        );
+    }
+
+    #[test]
+    fn debug_fields_print_outputs_and_opaque_inputs_as_placeholders() {
+        let function = mock::function(
+            "SetName",
+            ptr::null_mut(),
+            &[
+                parameter("Slot", EClassCastFlags::CASTCLASS_FIntProperty, &[EPropertyFlags::CPF_Parm]),
+                parameter("Name", EClassCastFlags::CASTCLASS_FStrProperty, &[EPropertyFlags::CPF_Parm]),
+                parameter("ReturnValue", EClassCastFlags::CASTCLASS_FBoolProperty, &[EPropertyFlags::CPF_Parm, EPropertyFlags::CPF_ReturnParm]),
+            ],
+        );
+
+        let parameters = unsafe { Parameters::new(function, ptr::null(), false) }.unwrap();
+        let mut out = List::<u8, 256>::new();
+        fmt::write(&mut out, format_args!("{}", DebugFields(&parameters))).unwrap();
+
+        assert_eq!(
+            str::from_utf8(out.as_slice()).unwrap(),
+            "\n            .field(\"Slot\", &self.Slot)\
+            \n            .field(\"Name\", &\"<common::FString>\")\
+            \n            .field(\"ReturnValue\", &\"<out>\")",
+        );
+    }
+}
@@ -0,0 +1,55 @@
+use super::{CleanedName, Error};
+use common::{List, UStruct};
+use core::cmp::Ordering;
+use core::fmt::Write;
+use core::ptr;
+
+// The structs and classes that `validate::validate()` checks, and whether each
+// is Blueprint-generated. `Generator::finish()` writes them sorted by full
+// name, which `validate()` looks them up by.
+const MAX_STRUCTS: usize = 1 << 16;
+
+static mut STRUCTS: List<(*const UStruct, bool), MAX_STRUCTS> = List::new();
+
+// Forgets every struct, for a new `Generator`.
+pub unsafe fn reset() {
+    (*ptr::addr_of_mut!(STRUCTS)).clear();
+}
+
+pub unsafe fn add(structure: *const UStruct, is_blueprint_generated: bool) -> Result<(), Error> {
+    (*ptr::addr_of_mut!(STRUCTS))
+        .push((structure, is_blueprint_generated))
+        .map_err(|_| Error::MaxValidatorChecks)
+}
+
+// Writes a `CHECKS` entry for each struct, by the same paths as
+// `StructGenerator` names the types by.
+pub unsafe fn write(mut out: impl Write) -> Result<(), Error> {
+    let structs = (*ptr::addr_of_mut!(STRUCTS)).as_mut_slice();
+    structs.sort_unstable_by(|&(a, _), &(b, _)| compare(a, b));
+
+    for &(structure, is_blueprint_generated) in structs.iter() {
+        write!(out, "    (\"{}\", ", *structure)?;
+
+        if is_blueprint_generated {
+            write!(out, "check::<crate::blueprint_generated::{}>", CleanedName::new((&*structure).NamePrivate))?;
+        } else {
+            write!(out, "check::<crate::{}::{}>", (*(*structure).package()).short_name(), (*structure).name())?;
+        }
+
+        writeln!(out, "),")?;
+    }
+
+    Ok(())
+}
+
+// By the bytes of the full name, like `validate()` compares them.
+fn compare(a: *const UStruct, b: *const UStruct) -> Ordering {
+    full_name(a).as_slice().cmp(full_name(b).as_slice())
+}
+
+fn full_name(structure: *const UStruct) -> List<u8, 1024> {
+    let mut name = List::new();
+    let _ = unsafe { write!(name, "{}", *structure) };
+    name
+}
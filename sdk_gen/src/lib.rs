@@ -4,13 +4,13 @@
 // #[link(name = "ucrt")]
 // extern {}
 
-#[link(name = "msvcrt")]
+#[cfg_attr(windows, link(name = "msvcrt"))]
 extern "C" {}
 
-#[link(name = "vcruntime")]
+#[cfg_attr(windows, link(name = "vcruntime"))]
 extern "C" {}
 
-use common::{list, timer, win, GUObjectArray, Hex, NamePoolData, Timer};
+use common::{list, timer, win, Console, GUObjectArray, Hex, LiveMemory, NamePoolData, Timer};
 use core::ffi::c_void;
 use core::fmt::{self, Write};
 use core::str;
@@ -19,9 +19,27 @@ mod buf_writer;
 use buf_writer::BufWriter;
 mod game;
 mod generator;
-use generator::Generator;
+use generator::{Generator, GeneratorOptions};
 mod util;
 
+// Tweak these to change what the SDK generator emits.
+const GENERATOR_OPTIONS: GeneratorOptions = GeneratorOptions {
+    ..GeneratorOptions::DEFAULT
+};
+
+// Where `common::log!` messages, including the generator's warnings, go.
+const LOG: &dyn common::Log = &common::ConsoleLog;
+
+// Print the generated code of one struct to the console on attach, as
+// `(package, name)`, e.g. `Some(("FSD", "PlayerCharacter"))`. See
+// `generator::generate_struct_by_name()`.
+const PRINT_STRUCT: Option<(&str, &str)> = None;
+
+// Only generate these packages and what they refer to, e.g. `&["FSD"]`, instead
+// of every package. Empty generates them all. See
+// `Generator::generate_packages()`.
+const PACKAGES: &[&str] = &[];
+
 #[derive(macros::NoPanicErrorDebug)]
 enum Error {
     Game(#[from] game::Error),
@@ -40,6 +58,7 @@ unsafe extern "system" fn _DllMainCRTStartup(dll: *mut c_void, reason: u32, _: *
 
 unsafe extern "system" fn on_attach(dll: *mut c_void) -> u32 {
     win::AllocConsole();
+    common::set_logger(LOG);
 
     timer::initialize_ticks_per_second();
 
@@ -59,6 +78,10 @@ unsafe fn run() -> Result<(), Error> {
     common::init_globals(&win::Module::current()?)?;
     dump_globals()?;
 
+    if let Some((package, name)) = PRINT_STRUCT {
+        generator::generate_struct_by_name(&LiveMemory, GENERATOR_OPTIONS, package, name, Console)?;
+    }
+
     if cfg!(feature = "gen_sdk") {
         generate_sdk()?;
     }
@@ -104,7 +127,16 @@ unsafe fn dump_objects() -> Result<(), Error> {
 
 unsafe fn generate_sdk() -> Result<(), Error> {
     let timer = Timer::new("generate sdk");
-    Generator::new()?.generate_sdk()?;
+    let mut generator = Generator::new(GENERATOR_OPTIONS)?;
+
+    if PACKAGES.is_empty() {
+        generator.generate_sdk()?;
+    } else {
+        generator.generate_packages(PACKAGES)?;
+    }
+
+    generator.finish()?;
     timer.stop();
+    generator.print_summary();
     Ok(())
 }